@@ -0,0 +1,21 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+pub mod webhook;
+pub use webhook::*;
+
+#[derive(Clone, Debug, Hash, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ChainAction {
+    Notify,
+    Webhook(Webhook),
+}
+
+impl ChainAction {
+    /// Check that the action can be carried out.
+    pub fn check(&self) -> Result<()> {
+        match self {
+            Self::Notify => Ok(()),
+            Self::Webhook(webhook) => webhook.check(),
+        }
+    }
+}
@@ -0,0 +1,434 @@
+use anyhow::{anyhow, ensure, Result};
+use hmac::{Hmac, Mac};
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use reqwest::header::{HeaderName, HeaderValue, CONTENT_TYPE};
+use reqwest::redirect::Policy;
+use reqwest::{Client, Url};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+use std::fmt::{self, Debug};
+use std::net::IpAddr;
+use std::sync::Arc;
+use tokio::time::{sleep, Duration};
+
+/// Header carrying the signature of the request body.
+pub const SIGNATURE_HEADER: &str = "X-Signature-256";
+
+/// Number of requests made before a delivery is given up on.
+pub const MAX_ATTEMPTS: u32 = 5;
+
+/// Delay before the first retry. It doubles after every failed attempt.
+pub const INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Time a request is given to get an answer before it counts as a failed attempt.
+pub const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Build the client webhook requests are made with. Hosts only resolve to their public
+/// addresses, unless they are on the operator's allow-list, and redirects are not followed, so
+/// a manifest cannot make the monitor post to its own host or network.
+pub fn webhook_client(allowed_hosts: impl IntoIterator<Item = String>) -> Client {
+    let resolver = PublicResolver {
+        allowed_hosts: allowed_hosts.into_iter().collect(),
+    };
+    Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .redirect(Policy::none())
+        .dns_resolver(Arc::new(resolver))
+        .build()
+        .expect("the webhook client configuration is valid")
+}
+
+/// Returns `true` if the address can be reached from the public internet, and is not a
+/// loopback, private, link-local or otherwise reserved address.
+fn is_public(address: IpAddr) -> bool {
+    match address {
+        IpAddr::V4(address) => {
+            let [first, second, ..] = address.octets();
+            !(address.is_unspecified()
+                || address.is_loopback()
+                || address.is_private()
+                || address.is_link_local()
+                || address.is_broadcast()
+                || address.is_documentation()
+                // Shared address space of carrier-grade NATs, 100.64.0.0/10.
+                || (first == 100 && second & 0xc0 == 64))
+        }
+        IpAddr::V6(address) => match address.to_ipv4_mapped() {
+            Some(mapped) => is_public(IpAddr::V4(mapped)),
+            None => {
+                let first = address.segments()[0];
+                !(address.is_unspecified()
+                    || address.is_loopback()
+                    // Unique local addresses, fc00::/7.
+                    || first & 0xfe00 == 0xfc00
+                    // Link-local addresses, fe80::/10.
+                    || first & 0xffc0 == 0xfe80)
+            }
+        },
+    }
+}
+
+/// Resolves webhook hosts, leaving out the addresses that are not public unless the host is on
+/// the allow-list.
+struct PublicResolver {
+    allowed_hosts: BTreeSet<String>,
+}
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let allowed = self.allowed_hosts.contains(name.as_str());
+        Box::pin(async move {
+            let host = name.as_str();
+            let addresses = tokio::net::lookup_host((host, 0))
+                .await?
+                .filter(|address| allowed || is_public(address.ip()))
+                .collect::<Vec<_>>();
+            if addresses.is_empty() {
+                return Err(format!("webhook host {host} has no public address").into());
+            }
+            Ok::<Addrs, Box<dyn Error + Send + Sync>>(Box::new(addresses.into_iter()))
+        })
+    }
+}
+
+/// Posts every event payload as JSON to an HTTP endpoint.
+#[derive(Clone, Hash, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Webhook {
+    // Endpoint the payloads are posted to.
+    pub url: String,
+    // Extra headers sent with every request.
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    // Key the request bodies are signed with.
+    pub secret: String,
+}
+
+impl Webhook {
+    /// Check that the URL and the headers are valid. A URL may not name an address that is not
+    /// public; host names are checked when they are resolved for each request.
+    pub fn check(&self) -> Result<()> {
+        let url = Url::parse(&self.url)
+            .map_err(|e| anyhow!("webhook URL {} is invalid: {e}", self.url))?;
+        ensure!(
+            matches!(url.scheme(), "http" | "https"),
+            "webhook URL {} is not an HTTP URL",
+            self.url
+        );
+        let host = url
+            .host_str()
+            .ok_or_else(|| anyhow!("webhook URL {} has no host", self.url))?;
+        if let Ok(address) = host.trim_start_matches('[').trim_end_matches(']').parse() {
+            ensure!(
+                is_public(address),
+                "webhook URL {} is not a public address",
+                self.url
+            );
+        }
+        ensure!(!self.secret.is_empty(), "webhook secret is empty");
+        for (name, value) in &self.headers {
+            ensure!(
+                HeaderName::try_from(name).is_ok() && HeaderValue::try_from(value).is_ok(),
+                "webhook header {name} is invalid"
+            );
+        }
+        Ok(())
+    }
+
+    /// Returns the signature header of a request body: `sha256=` followed by the hex encoded
+    /// HMAC-SHA256 of the body, keyed with the secret.
+    pub fn signature(&self, body: &[u8]) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(self.secret.as_bytes())
+            .expect("HMAC takes keys of any length");
+        mac.update(body);
+        format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+    }
+
+    /// Post the body until the endpoint answers with a 2xx status, waiting `backoff` before the
+    /// first retry and twice as long before each one after it. A request that fails to send or
+    /// times out under the client's timeout counts as a failed attempt.
+    pub async fn deliver(
+        &self,
+        client: &Client,
+        body: Vec<u8>,
+        backoff: Duration,
+    ) -> DeliveryStatus {
+        let mut status = DeliveryStatus::new(self.url.clone());
+        let mut delay = backoff;
+        loop {
+            status.attempts += 1;
+            match self.post(client, body.clone()).await {
+                Ok(()) => {
+                    status.state = DeliveryState::Delivered;
+                    status.error = None;
+                    return status;
+                }
+                Err(error) => status.error = Some(error.to_string()),
+            }
+            if status.attempts >= MAX_ATTEMPTS {
+                status.state = DeliveryState::Failed;
+                return status;
+            }
+            sleep(delay).await;
+            delay *= 2;
+        }
+    }
+
+    /// Make a single request.
+    async fn post(&self, client: &Client, body: Vec<u8>) -> Result<()> {
+        let mut request = client
+            .post(&self.url)
+            .header(CONTENT_TYPE, "application/json")
+            .header(SIGNATURE_HEADER, self.signature(&body));
+        for (name, value) in &self.headers {
+            request = request.header(name, value);
+        }
+        let response = request.body(body).send().await?;
+        ensure!(
+            response.status().is_success(),
+            "endpoint answered {}",
+            response.status()
+        );
+        Ok(())
+    }
+}
+
+// The secret is left out so it never reaches the logs.
+impl Debug for Webhook {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Webhook")
+            .field("url", &self.url)
+            .field("headers", &self.headers)
+            .field("secret", &"<redacted>")
+            .finish()
+    }
+}
+
+/// How far the delivery of an event to a webhook got.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum DeliveryState {
+    Pending,
+    Delivered,
+    Failed,
+}
+
+/// Delivery of an event to a webhook.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DeliveryStatus {
+    // Endpoint the event is posted to.
+    url: String,
+    // Whether the delivery is still being attempted, succeeded or was given up on.
+    state: DeliveryState,
+    // Number of requests made.
+    attempts: u32,
+    // Why the last request failed.
+    error: Option<String>,
+}
+
+impl DeliveryStatus {
+    pub fn new(url: String) -> DeliveryStatus {
+        DeliveryStatus {
+            url,
+            state: DeliveryState::Pending,
+            attempts: 0,
+            error: None,
+        }
+    }
+
+    /// Get the endpoint the event is posted to.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Get how far the delivery got.
+    pub fn state(&self) -> DeliveryState {
+        self.state
+    }
+
+    /// Get the number of requests made.
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    /// Get why the last request failed.
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Bytes;
+    use axum::extract::State;
+    use axum::http::{HeaderMap, StatusCode};
+    use axum::routing::post;
+    use axum::Router;
+    use parking_lot::Mutex;
+    use std::sync::Arc;
+
+    type Requests = Arc<Mutex<Vec<(HeaderMap, Bytes)>>>;
+
+    /// Serve the test endpoints on a local port and return its address.
+    async fn serve(requests: Requests) -> std::net::SocketAddr {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let app = Router::new()
+            .route("/events", post(flaky))
+            .route("/failing", post(failing))
+            .route("/slow", post(slow))
+            .with_state(requests);
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        address
+    }
+
+    /// Build a client that may post to the test endpoints.
+    fn local_client() -> Client {
+        webhook_client(["localhost".to_string()])
+    }
+
+    fn webhook(url: String) -> Webhook {
+        Webhook {
+            url,
+            headers: BTreeMap::from([("X-Api-Key".to_string(), "key".to_string())]),
+            secret: "hunter2".to_string(),
+        }
+    }
+
+    /// Fails the first request and accepts the rest.
+    async fn flaky(
+        State(requests): State<Requests>,
+        headers: HeaderMap,
+        body: Bytes,
+    ) -> StatusCode {
+        let mut requests = requests.lock();
+        requests.push((headers, body));
+        match requests.len() {
+            1 => StatusCode::INTERNAL_SERVER_ERROR,
+            _ => StatusCode::OK,
+        }
+    }
+
+    /// Fails every request.
+    async fn failing(
+        State(requests): State<Requests>,
+        headers: HeaderMap,
+        body: Bytes,
+    ) -> StatusCode {
+        requests.lock().push((headers, body));
+        StatusCode::SERVICE_UNAVAILABLE
+    }
+
+    /// Answers long after the client has given up.
+    async fn slow() -> StatusCode {
+        sleep(Duration::from_secs(5)).await;
+        StatusCode::OK
+    }
+
+    #[test]
+    fn test_webhook_signature() {
+        // Test case 2 of RFC 4231.
+        let webhook = Webhook {
+            url: "https://example.com/events".to_string(),
+            headers: BTreeMap::new(),
+            secret: "Jefe".to_string(),
+        };
+        assert_eq!(
+            webhook.signature(b"what do ya want for nothing?"),
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+        assert_ne!(
+            webhook.signature(b"what do ya want for nothing?"),
+            webhook.signature(b"what do ya want for nothing!")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_webhook_delivery() {
+        let requests = Requests::default();
+        let address = serve(requests.clone()).await;
+        let webhook = webhook(format!("http://localhost:{}/events", address.port()));
+        webhook.check().unwrap();
+
+        let body = br#"{"event_type":"largeTransfers"}"#.to_vec();
+        let client = local_client();
+        let status = webhook
+            .deliver(&client, body.clone(), Duration::from_millis(10))
+            .await;
+        assert_eq!(status.state(), DeliveryState::Delivered);
+        assert_eq!(status.attempts(), 2);
+        assert!(status.error().is_none());
+
+        let requests = requests.lock();
+        assert_eq!(requests.len(), 2);
+        let (headers, received) = &requests[1];
+        assert_eq!(received.as_ref(), body.as_slice());
+        assert_eq!(headers["x-api-key"], "key");
+        assert_eq!(headers[SIGNATURE_HEADER], webhook.signature(&body).as_str());
+
+        // The secret does not reach the logs.
+        assert!(!format!("{webhook:?}").contains("hunter2"));
+    }
+
+    #[tokio::test]
+    async fn test_webhook_gives_up() {
+        let requests = Requests::default();
+        let address = serve(requests.clone()).await;
+        let body = br#"{"event_type":"largeTransfers"}"#.to_vec();
+
+        // Every request is answered with an error.
+        let failing = webhook(format!("http://localhost:{}/failing", address.port()));
+        let status = failing
+            .deliver(&local_client(), body.clone(), Duration::from_millis(1))
+            .await;
+        assert_eq!(status.state(), DeliveryState::Failed);
+        assert_eq!(status.attempts(), MAX_ATTEMPTS);
+        assert!(status.error().unwrap().contains("503"));
+        assert_eq!(requests.lock().len(), MAX_ATTEMPTS as usize);
+
+        // Every request times out.
+        let slow = webhook(format!("http://localhost:{}/slow", address.port()));
+        let client = Client::builder()
+            .timeout(Duration::from_millis(50))
+            .build()
+            .unwrap();
+        let status = slow.deliver(&client, body, Duration::from_millis(1)).await;
+        assert_eq!(status.state(), DeliveryState::Failed);
+        assert_eq!(status.attempts(), MAX_ATTEMPTS);
+        assert!(status.error().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_webhook_rejects_local_addresses() {
+        for url in [
+            "http://127.0.0.1/events",
+            "http://2130706433/events",
+            "http://10.0.0.1/events",
+            "http://192.168.1.1/events",
+            "http://169.254.169.254/latest/meta-data",
+            "http://[::1]/events",
+            "http://[fd00::1]/events",
+            "http://[::ffff:127.0.0.1]/events",
+        ] {
+            assert!(webhook(url.to_string()).check().is_err(), "{url}");
+        }
+        webhook("https://93.184.215.14/events".to_string())
+            .check()
+            .unwrap();
+
+        // Host names are checked when they are resolved, so only allowed hosts reach the local
+        // network.
+        let requests = Requests::default();
+        let address = serve(requests.clone()).await;
+        let local = webhook(format!("http://localhost:{}/events", address.port()));
+        local.check().unwrap();
+        let body = br#"{"event_type":"largeTransfers"}"#.to_vec();
+        let status = local
+            .deliver(&webhook_client(Vec::new()), body, Duration::from_millis(1))
+            .await;
+        assert_eq!(status.state(), DeliveryState::Failed);
+        assert!(status.error().is_some());
+        assert!(requests.lock().is_empty());
+    }
+}
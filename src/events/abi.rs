@@ -0,0 +1,418 @@
+use super::{
+    Condition, EventKind, EventManifest, NamePattern, Predicate, Segment, Selector, ValueSource,
+};
+use snarkvm::prelude::{Network, PlaintextType, ProgramID, ValueType};
+use snarkvm::synthesizer::program::Program;
+
+use anyhow::{anyhow, bail, ensure, Result};
+use std::str::FromStr;
+
+impl<N: Network> EventManifest<N> {
+    /// Returns the deployed programs the manifest can be checked against: the program of a mapping
+    /// filter, the programs named by a program pattern without wildcards, or those named that way
+    /// by the conditions of a composite or sequence. Deployment, block and committee manifests,
+    /// and manifests whose transitions are selected by conditions, are not checked.
+    pub fn checked_programs(&self) -> Result<Vec<ProgramID<N>>> {
+        match &self.kind {
+            EventKind::Mapping(filter) => Ok(vec![filter.program]),
+            EventKind::Transition
+            | EventKind::Record
+            | EventKind::RecordSpent(_)
+            | EventKind::Aggregate(_) => {
+                match self.program.as_ref().and_then(NamePattern::literals) {
+                    Some(names) => names.into_iter().map(ProgramID::from_str).collect(),
+                    None => Ok(vec![]),
+                }
+            }
+            EventKind::Composite(filter) => condition_programs(&filter.transitions),
+            EventKind::Sequence(filter) => condition_programs(&filter.steps),
+            _ => Ok(vec![]),
+        }
+    }
+
+    /// Check the manifest against a program's declarations and return every problem found. The
+    /// function pattern must match a declared function, and each input, output and selector must
+    /// point at a constant or public value whose type the predicate can compare in at least one
+    /// function the pattern matches, or in any function if there is no pattern. The conditions
+    /// of a composite or sequence are checked the same way when they name the program and a
+    /// function.
+    pub fn check_program(&self, program: &Program<N>) -> Vec<String> {
+        let mut problems = vec![];
+        let prefix = format!("Event '{}' {}", self.name, program.id());
+
+        match &self.kind {
+            EventKind::Mapping(filter) => {
+                let Ok(mapping) = program.get_mapping(&filter.mapping) else {
+                    problems.push(format!(
+                        "{prefix}: mapping '{}' is not declared",
+                        filter.mapping
+                    ));
+                    return problems;
+                };
+                let values = [
+                    ("key", &filter.key, mapping.key().plaintext_type()),
+                    ("value", &filter.value, mapping.value().plaintext_type()),
+                ];
+                for (name, predicate, plaintext_type) in values {
+                    if let Some(predicate) = predicate {
+                        if let Err(e) = check_type(predicate, plaintext_type) {
+                            problems.push(format!("{prefix}/{} {name}: {e}", filter.mapping));
+                        }
+                    }
+                }
+                return problems;
+            }
+            EventKind::Composite(filter) => {
+                for (index, condition) in filter.transitions.iter().enumerate() {
+                    let scope = format!(" transition condition {index}");
+                    problems.extend(check_condition(program, &prefix, &scope, condition));
+                }
+                return problems;
+            }
+            EventKind::Sequence(filter) => {
+                for (index, step) in filter.steps.iter().enumerate() {
+                    let scope = format!(" sequence step {index}");
+                    problems.extend(check_condition(program, &prefix, &scope, step));
+                }
+                return problems;
+            }
+            _ => {}
+        }
+
+        let inputs = self.inputs.iter().flatten().map(|(index, predicate)| {
+            let selector = Selector::new(ValueSource::Input, *index, vec![]);
+            (format!("input {index}"), selector, predicate)
+        });
+        let outputs = self.outputs.iter().flatten().map(|(index, predicate)| {
+            let selector = Selector::new(ValueSource::Output, *index, vec![]);
+            (format!("output {index}"), selector, predicate)
+        });
+        let selectors = self
+            .selectors
+            .iter()
+            .flatten()
+            .map(|(selector, predicate)| (selector.to_string(), selector.clone(), predicate));
+        let values = inputs.chain(outputs).chain(selectors).collect::<Vec<_>>();
+        // Without a function pattern, the values are checked against every function.
+        match &self.function {
+            Some(pattern) => check_functions(program, &prefix, "", &[pattern], &values),
+            None if values.is_empty() => problems,
+            None => check_functions(program, &prefix, "", &[], &values),
+        }
+    }
+}
+
+/// Returns the programs named without wildcards by the `Program` leaves every transition
+/// matching one of the conditions satisfies.
+fn condition_programs<N: Network>(conditions: &[Condition<N>]) -> Result<Vec<ProgramID<N>>> {
+    let mut program_ids = vec![];
+    for leaf in conditions.iter().flat_map(conjuncts) {
+        let Condition::Program(pattern) = leaf else {
+            continue;
+        };
+        for name in pattern.literals().into_iter().flatten() {
+            let program_id = ProgramID::from_str(name)?;
+            if !program_ids.contains(&program_id) {
+                program_ids.push(program_id);
+            }
+        }
+    }
+    Ok(program_ids)
+}
+
+/// Returns the leaves reached from the condition through `All` nodes, which every transition
+/// matching the condition satisfies.
+fn conjuncts<N: Network>(condition: &Condition<N>) -> Vec<&Condition<N>> {
+    match condition {
+        Condition::All(conditions) => conditions.iter().flat_map(conjuncts).collect(),
+        leaf => vec![leaf],
+    }
+}
+
+/// Check the value leaves of a condition that requires the program and a function. Conditions
+/// that do not name both could be met by other calls, and are not checked.
+fn check_condition<N: Network>(
+    program: &Program<N>,
+    prefix: &str,
+    scope: &str,
+    condition: &Condition<N>,
+) -> Vec<String> {
+    let leaves = conjuncts(condition);
+    let names_program = leaves
+        .iter()
+        .any(|leaf| matches!(leaf, Condition::Program(pattern) if pattern.matches(program.id())));
+    let patterns = leaves
+        .iter()
+        .filter_map(|leaf| match leaf {
+            Condition::Function(pattern) => Some(pattern),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    if !names_program || patterns.is_empty() {
+        return vec![];
+    }
+    let values = leaves
+        .iter()
+        .filter_map(|leaf| match leaf {
+            Condition::Value(selector, predicate) => {
+                Some((selector.to_string(), selector.clone(), predicate))
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    check_functions(program, prefix, scope, &patterns, &values)
+}
+
+/// Check each labelled value against the declarations of the functions of the program matching
+/// all the patterns. A value is a problem only if none of them can accept it, since the others
+/// are simply never matched. The scope is appended to the function name in problems.
+fn check_functions<N: Network>(
+    program: &Program<N>,
+    prefix: &str,
+    scope: &str,
+    patterns: &[&NamePattern],
+    values: &[(String, Selector<N>, &Predicate<N>)],
+) -> Vec<String> {
+    let functions = program
+        .functions()
+        .values()
+        .filter(|function| {
+            patterns
+                .iter()
+                .all(|pattern| pattern.matches(function.name()))
+        })
+        .collect::<Vec<_>>();
+    if functions.is_empty() {
+        return vec![format!(
+            "{prefix}{scope}: no function matches the function pattern"
+        )];
+    }
+    let mut problems = vec![];
+    for (label, selector, predicate) in values {
+        let mut errors = vec![];
+        for function in &functions {
+            let declared = match selector.source() {
+                ValueSource::Input => function
+                    .inputs()
+                    .iter()
+                    .nth(selector.index())
+                    .map(|input| input.value_type()),
+                ValueSource::Output => function
+                    .outputs()
+                    .iter()
+                    .nth(selector.index())
+                    .map(|output| output.value_type()),
+            };
+            match check_value(program, declared, selector.segments(), predicate) {
+                Ok(()) => {
+                    errors.clear();
+                    break;
+                }
+                Err(e) => errors.push((function.name(), e)),
+            }
+        }
+        match errors.as_slice() {
+            [] => {}
+            [(name, e)] => problems.push(format!("{prefix}/{name}{scope} {label}: {e}")),
+            errors => problems.push(format!(
+                "{prefix}{scope} {label}: no function accepts it ({})",
+                errors
+                    .iter()
+                    .map(|(name, e)| format!("{name}: {e}"))
+                    .collect::<Vec<_>>()
+                    .join("; ")
+            )),
+        }
+    }
+    problems
+}
+
+/// Check that a declared input or output is visible to the monitor, that the path exists in its
+/// type, and that the predicate can compare the value it reaches. Paths into futures are not
+/// checked, since argument types are only known once the future is decoded.
+fn check_value<N: Network>(
+    program: &Program<N>,
+    value_type: Option<&ValueType<N>>,
+    segments: &[Segment<N>],
+    predicate: &Predicate<N>,
+) -> Result<()> {
+    let plaintext_type = match value_type {
+        None => bail!("the function has no value at this index"),
+        Some(ValueType::Constant(plaintext_type) | ValueType::Public(plaintext_type)) => {
+            plaintext_type
+        }
+        Some(ValueType::Private(_)) => bail!("the value is private and never matches"),
+        Some(ValueType::Record(_) | ValueType::ExternalRecord(_)) => {
+            bail!("the value is a record and never matches")
+        }
+        Some(ValueType::Future(_)) if !segments.is_empty() => return Ok(()),
+        Some(ValueType::Future(_)) => bail!("the value is a future, select one of its arguments"),
+    };
+    let plaintext_type =
+        segments
+            .iter()
+            .try_fold(plaintext_type.clone(), |plaintext_type, segment| {
+                match (segment, &plaintext_type) {
+                    (Segment::Member(member), PlaintextType::Struct(name)) => program
+                        .get_struct(name)?
+                        .members()
+                        .get(member)
+                        .cloned()
+                        .ok_or_else(|| anyhow!("struct '{name}' has no member '{member}'")),
+                    (Segment::Index(index), PlaintextType::Array(array)) => {
+                        ensure!(
+                            *index < **array.length() as usize,
+                            "index {index} is out of range for {plaintext_type}"
+                        );
+                        Ok(array.next_element_type().clone())
+                    }
+                    (Segment::Member(member), _) => {
+                        bail!("{plaintext_type} has no member '{member}'")
+                    }
+                    (Segment::Index(index), _) => {
+                        bail!("{plaintext_type} cannot be indexed by {index}")
+                    }
+                }
+            })?;
+    check_type(predicate, &plaintext_type)
+}
+
+/// Check that the predicate's operands have the declared type. Only equality and set membership
+/// apply to structs and arrays.
+fn check_type<N: Network>(
+    predicate: &Predicate<N>,
+    plaintext_type: &PlaintextType<N>,
+) -> Result<()> {
+    match plaintext_type {
+        PlaintextType::Literal(literal_type) => predicate.check_literal_type(*literal_type),
+        _ => {
+            ensure!(
+                matches!(
+                    predicate,
+                    Predicate::Eq(_) | Predicate::Ne(_) | Predicate::In(_)
+                ),
+                "{plaintext_type} values do not support ordering comparisons"
+            );
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::prelude::MainnetV0;
+
+    type CurrentNetwork = MainnetV0;
+
+    #[test]
+    fn test_check_program() {
+        let program = Program::<CurrentNetwork>::from_str(
+            r"program token.aleo;
+
+struct info:
+    owner as address;
+    amounts as [u64; 2u32];
+
+function send:
+    input r0 as address.public;
+    input r1 as u64.private;
+    input r2 as info.public;
+    output r2 as info.public;
+
+function send_public:
+    input r0 as address.public;
+    input r1 as u64.public;",
+        )
+        .unwrap();
+        let manifest = serde_json::from_value::<EventManifest<CurrentNetwork>>(serde_json::json!({
+            "name": "sends",
+            "description": "",
+            "function": "send",
+            "program": "token.aleo",
+            "inputs": {"1": {"Gt": "5u64"}, "3": {"Eq": "1u64"}},
+            "outputs": {"0": {"Lt": "5u64"}},
+            "selectors": {
+                "input[2].amounts[1]": {"Gt": "5u64"},
+                "input[2].amounts[2]": {"Gt": "5u64"},
+                "input[2].owner": {"Gt": "5u64"},
+            },
+            "actions": [],
+        }))
+        .unwrap();
+        assert_eq!(
+            manifest.checked_programs().unwrap(),
+            vec![ProgramID::from_str("token.aleo").unwrap()]
+        );
+
+        let problems = manifest.check_program(&program);
+        // Private input 1, missing input 3, ordering on a struct output, an out of range element
+        // and a mistyped address comparison. `amounts[1]` is fine.
+        assert_eq!(problems.len(), 5, "{problems:#?}");
+        assert!(problems[0].starts_with("Event 'sends' token.aleo/send input 1"));
+
+        let mut unknown = manifest.clone();
+        unknown.function = Some(NamePattern::from_str("sned").unwrap());
+        assert_eq!(unknown.check_program(&program).len(), 1);
+
+        // With a wildcard pattern or none, a value is a problem only if no function accepts it.
+        // Input 1 is private in `send` but public in `send_public`.
+        let mut wildcard =
+            serde_json::from_value::<EventManifest<CurrentNetwork>>(serde_json::json!({
+                "name": "sends",
+                "description": "",
+                "function": "send*",
+                "program": "token.aleo",
+                "inputs": {"0": {"Gt": "5u64"}, "1": {"Gt": "5u64"}},
+                "actions": [],
+            }))
+            .unwrap();
+        let problems = wildcard.check_program(&program);
+        assert_eq!(problems.len(), 1, "{problems:#?}");
+        assert!(problems[0].starts_with("Event 'sends' token.aleo input 0: no function accepts"));
+        wildcard.function = None;
+        assert_eq!(wildcard.check_program(&program), problems);
+
+        // Sequence steps are checked when they name the program and a function.
+        let sequence = serde_json::from_value::<EventManifest<CurrentNetwork>>(serde_json::json!({
+            "name": "resends",
+            "description": "",
+            "kind": {"Sequence": {
+                "steps": [
+                    {"All": [
+                        {"Program": "token.aleo"},
+                        {"Function": "send"},
+                        {"Value": ["input[1]", {"Gt": "5u64"}]},
+                    ]},
+                    {"All": [
+                        {"Program": "token.aleo"},
+                        {"Function": "sned"},
+                    ]},
+                    {"All": [
+                        {"Program": "other.aleo"},
+                        {"Function": "send"},
+                        {"Value": ["input[1]", {"Gt": "5u64"}]},
+                    ]},
+                    {"Any": [
+                        {"Program": "token.aleo"},
+                        {"Value": ["input[1]", {"Gt": "5u64"}]},
+                    ]},
+                ],
+                "within": 10,
+            }},
+            "actions": [],
+        }))
+        .unwrap();
+        assert_eq!(
+            sequence.checked_programs().unwrap(),
+            vec![
+                ProgramID::from_str("token.aleo").unwrap(),
+                ProgramID::from_str("other.aleo").unwrap()
+            ]
+        );
+        let problems = sequence.check_program(&program);
+        assert_eq!(problems.len(), 2, "{problems:#?}");
+        assert!(problems[0].starts_with("Event 'resends' token.aleo/send sequence step 0 input[1]"));
+        assert!(problems[1].starts_with("Event 'resends' token.aleo sequence step 1"));
+    }
+}
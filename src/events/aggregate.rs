@@ -0,0 +1,147 @@
+use super::Selector;
+use snarkvm::prelude::{Literal, Network, Plaintext};
+
+use anyhow::{ensure, Result};
+use serde::{Deserialize, Serialize};
+
+/// How the samples in a window are combined.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum AggregateFunction {
+    Sum,
+    Count,
+    Min,
+    Max,
+    Average,
+}
+
+impl AggregateFunction {
+    /// Combine the samples, or return `None` if there are none and the function needs at least
+    /// one. Sums saturate and averages round toward zero.
+    pub fn apply(&self, samples: impl Iterator<Item = i128>) -> Option<i128> {
+        match self {
+            Self::Sum => Some(samples.fold(0i128, i128::saturating_add)),
+            Self::Count => Some(samples.count() as i128),
+            Self::Min => samples.min(),
+            Self::Max => samples.max(),
+            Self::Average => {
+                let (sum, count) = samples.fold((0i128, 0i128), |(sum, count), sample| {
+                    (sum.saturating_add(sample), count + 1)
+                });
+                (count > 0).then(|| sum / count)
+            }
+        }
+    }
+}
+
+/// How blocks are grouped into windows.
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum WindowKind {
+    // The last `window` blocks, moving forward one block at a time.
+    #[default]
+    Sliding,
+    // Consecutive blocks of `window` heights starting at multiples of `window`.
+    Tumbling,
+}
+
+/// A bound the aggregate is compared against.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum Threshold {
+    Above(i128),
+    AtLeast(i128),
+    Below(i128),
+    AtMost(i128),
+}
+
+impl Threshold {
+    /// Returns `true` if the aggregate is past the threshold.
+    pub fn is_met(&self, aggregate: i128) -> bool {
+        match self {
+            Self::Above(bound) => aggregate > *bound,
+            Self::AtLeast(bound) => aggregate >= *bound,
+            Self::Below(bound) => aggregate < *bound,
+            Self::AtMost(bound) => aggregate <= *bound,
+        }
+    }
+}
+
+/// Aggregates a numeric value of the transitions the manifest matches over a window of blocks,
+/// and reports when the aggregate crosses a threshold.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(bound(serialize = "N: Serialize", deserialize = "N: for<'a> Deserialize<'a>"))]
+pub struct AggregateFilter<N: Network> {
+    pub function: AggregateFunction,
+    // Integer value aggregated, e.g. `input[1]`. Not needed for `Count`.
+    pub value: Option<Selector<N>>,
+    // Window size in blocks.
+    pub window: u32,
+    #[serde(default)]
+    pub window_kind: WindowKind,
+    pub threshold: Threshold,
+}
+
+impl<N: Network> AggregateFilter<N> {
+    /// Check the window size and that the value selector is set when needed.
+    pub fn check(&self) -> Result<()> {
+        ensure!(
+            self.window > 0,
+            "Aggregate window must be at least one block"
+        );
+        ensure!(
+            self.value.is_some() || self.function == AggregateFunction::Count,
+            "Aggregate {:?} needs a value selector",
+            self.function
+        );
+        Ok(())
+    }
+}
+
+/// Convert an integer plaintext to an `i128`, or return `None` for other values and `u128`
+/// values too large to fit.
+pub fn integer_value<N: Network>(plaintext: &Plaintext<N>) -> Option<i128> {
+    let Plaintext::Literal(literal, _) = plaintext else {
+        return None;
+    };
+    match literal {
+        Literal::I8(value) => Some(**value as i128),
+        Literal::I16(value) => Some(**value as i128),
+        Literal::I32(value) => Some(**value as i128),
+        Literal::I64(value) => Some(**value as i128),
+        Literal::I128(value) => Some(**value),
+        Literal::U8(value) => Some(**value as i128),
+        Literal::U16(value) => Some(**value as i128),
+        Literal::U32(value) => Some(**value as i128),
+        Literal::U64(value) => Some(**value as i128),
+        Literal::U128(value) => i128::try_from(**value).ok(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::prelude::MainnetV0;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_aggregate() {
+        let samples = [5i128, 1, 9];
+        assert_eq!(AggregateFunction::Sum.apply(samples.into_iter()), Some(15));
+        assert_eq!(AggregateFunction::Count.apply(samples.into_iter()), Some(3));
+        assert_eq!(AggregateFunction::Min.apply(samples.into_iter()), Some(1));
+        assert_eq!(AggregateFunction::Max.apply(samples.into_iter()), Some(9));
+        assert_eq!(
+            AggregateFunction::Average.apply(samples.into_iter()),
+            Some(5)
+        );
+        assert_eq!(AggregateFunction::Average.apply(std::iter::empty()), None);
+        assert_eq!(AggregateFunction::Count.apply(std::iter::empty()), Some(0));
+
+        assert!(Threshold::Above(14).is_met(15));
+        assert!(!Threshold::AtMost(14).is_met(15));
+
+        let amount = Plaintext::<MainnetV0>::from_str("1000000u64").unwrap();
+        assert_eq!(integer_value(&amount), Some(1_000_000));
+        let flag = Plaintext::<MainnetV0>::from_str("true").unwrap();
+        assert_eq!(integer_value(&flag), None);
+    }
+}
@@ -0,0 +1,66 @@
+use super::Predicate;
+use snarkvm::prelude::{Address, LiteralType, Network};
+
+use anyhow::{anyhow, ensure, Result};
+use serde::{Deserialize, Serialize};
+
+/// Selects blocks by their header and contents. Every set condition must hold, and a filter with
+/// none set fires on every block. The manifest's transition filters are not used.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(bound(serialize = "N: Serialize", deserialize = "N: for<'a> Deserialize<'a>"))]
+pub struct BlockFilter<N: Network> {
+    // Number of seconds since the previous block that must be exceeded.
+    pub timestamp_gap: Option<i64>,
+    // Whether the block must hold no transactions, or at least one.
+    pub empty: Option<bool>,
+    // Condition on the number of transactions, with `u32` operands.
+    pub transactions: Option<Predicate<N>>,
+    // Only blocks whose coinbase target differs from the previous block's.
+    #[serde(default)]
+    pub coinbase_target_changed: bool,
+    // Only blocks holding a puzzle solution from this prover.
+    pub prover: Option<Address<N>>,
+}
+
+impl<N: Network> BlockFilter<N> {
+    /// Check the timestamp gap and that the transaction count predicate compares `u32` values.
+    pub fn check(&self) -> Result<()> {
+        if let Some(gap) = self.timestamp_gap {
+            ensure!(gap > 0, "Block timestamp gap must be positive");
+        }
+        if let Some(predicate) = &self.transactions {
+            predicate
+                .check()
+                .and_then(|_| predicate.check_literal_type(LiteralType::U32))
+                .map_err(|e| anyhow!("block transactions: {e}"))?;
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if there is no timestamp gap condition, or more than `timestamp_gap`
+    /// seconds passed between the previous block and this one. The genesis block has no previous
+    /// block and never matches a gap.
+    pub fn matches_timestamp(&self, timestamp: i64, previous: Option<i64>) -> bool {
+        self.timestamp_gap.is_none_or(|gap| {
+            previous.is_some_and(|previous| timestamp.saturating_sub(previous) > gap)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::prelude::MainnetV0;
+
+    #[test]
+    fn test_timestamp_gap() {
+        let filter = BlockFilter::<MainnetV0> {
+            timestamp_gap: Some(60),
+            ..Default::default()
+        };
+        assert!(filter.matches_timestamp(1_061, Some(1_000)));
+        assert!(!filter.matches_timestamp(1_060, Some(1_000)));
+        assert!(!filter.matches_timestamp(1_000, None));
+        assert!(BlockFilter::<MainnetV0>::default().matches_timestamp(1_000, None));
+    }
+}
@@ -0,0 +1,76 @@
+use snarkvm::prelude::{Address, Network};
+
+use anyhow::{ensure, Result};
+use serde::{Deserialize, Serialize};
+
+/// A change to a validator's committee membership between two blocks.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum CommitteeChange {
+    // The validator joined the committee.
+    Added,
+    // The validator left the committee.
+    Removed,
+    // The validator's stake changed.
+    Stake,
+    // The validator's commission changed.
+    Commission,
+    // The validator opened or closed itself to delegators.
+    IsOpen,
+}
+
+/// Selects changes to the committee between a block and the one before it.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(bound(serialize = "N: Serialize", deserialize = "N: for<'a> Deserialize<'a>"))]
+pub struct CommitteeFilter<N: Network> {
+    // Changes to report. All are reported if not set.
+    pub changes: Option<Vec<CommitteeChange>>,
+    // Validators to watch. All are watched if not set.
+    pub validators: Option<Vec<Address<N>>>,
+    // Smallest stake change reported, in microcredits. Staking rewards change every validator's
+    // stake in every block, so it is required when stake changes are selected.
+    pub stake_change: Option<u64>,
+}
+
+impl<N: Network> CommitteeFilter<N> {
+    /// Check that the change and validator lists are not empty, and that stake changes have a
+    /// threshold if they are selected.
+    pub fn check(&self) -> Result<()> {
+        if let Some(changes) = &self.changes {
+            ensure!(!changes.is_empty(), "Committee change list is empty");
+        }
+        let stake_selected = self
+            .changes
+            .as_ref()
+            .is_none_or(|changes| changes.contains(&CommitteeChange::Stake));
+        ensure!(
+            !stake_selected || self.stake_change.is_some(),
+            "Committee stake changes need a stake_change threshold, since staking rewards change \
+             every validator's stake in every block"
+        );
+        if let Some(validators) = &self.validators {
+            ensure!(!validators.is_empty(), "Committee validator list is empty");
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if the change to the validator should be reported. Stake changes smaller
+    /// than the threshold are skipped.
+    pub fn includes(
+        &self,
+        validator: &Address<N>,
+        change: CommitteeChange,
+        stake: (u64, u64),
+    ) -> bool {
+        self.changes
+            .as_ref()
+            .is_none_or(|changes| changes.contains(&change))
+            && self
+                .validators
+                .as_ref()
+                .is_none_or(|validators| validators.contains(validator))
+            && (change != CommitteeChange::Stake
+                || self
+                    .stake_change
+                    .is_none_or(|threshold| stake.0.abs_diff(stake.1) >= threshold))
+    }
+}
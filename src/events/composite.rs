@@ -0,0 +1,206 @@
+use super::Condition;
+use snarkvm::ledger::block::{Transaction, Transition};
+use snarkvm::prelude::Network;
+
+use anyhow::{anyhow, ensure, Result};
+use serde::{Deserialize, Serialize};
+
+/// Selects transactions holding a set of transitions that each match their own condition, such
+/// as a `credits.aleo/transfer_public_as_signer` followed by a call into a token program.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(bound(serialize = "N: Serialize", deserialize = "N: for<'a> Deserialize<'a>"))]
+pub struct CompositeFilter<N: Network> {
+    // Conditions that must each be matched by a different transition.
+    pub transitions: Vec<Condition<N>>,
+    // Whether the matching transitions must appear in the listed order.
+    #[serde(default)]
+    pub ordered: bool,
+}
+
+impl<N: Network> CompositeFilter<N> {
+    /// Check that there are between one condition and as many as a transaction holds
+    /// transitions, and every condition is valid.
+    pub fn check(&self) -> Result<()> {
+        ensure!(
+            !self.transitions.is_empty(),
+            "Composite event has no transition conditions"
+        );
+        ensure!(
+            self.transitions.len() <= Transaction::<N>::MAX_TRANSITIONS,
+            "Composite event has {} transition conditions, but a transaction holds at most {}",
+            self.transitions.len(),
+            Transaction::<N>::MAX_TRANSITIONS
+        );
+        for (index, condition) in self.transitions.iter().enumerate() {
+            condition
+                .check()
+                .map_err(|e| anyhow!("transition condition {index}: {e}"))?;
+        }
+        Ok(())
+    }
+
+    /// Returns the index of the transition matching each condition, if every condition is matched
+    /// by a different transition.
+    pub fn match_transitions(&self, transitions: &[&Transition<N>]) -> Option<Vec<usize>> {
+        let mut matched = Vec::with_capacity(self.transitions.len());
+        if self.ordered {
+            // Taking the earliest match for each condition leaves the most room for the rest.
+            let mut next = 0;
+            for condition in &self.transitions {
+                let index = (next..transitions.len())
+                    .find(|index| condition.evaluate(transitions[*index]))?;
+                matched.push(index);
+                next = index + 1;
+            }
+            Some(matched)
+        } else if self.transitions.len() <= transitions.len() {
+            // Evaluate every condition against every transition once, then find a transition
+            // for each condition by augmenting paths.
+            let satisfies = self
+                .transitions
+                .iter()
+                .map(|condition| {
+                    transitions
+                        .iter()
+                        .map(|transition| condition.evaluate(transition))
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>();
+            let mut assigned = vec![None; transitions.len()];
+            for condition in 0..self.transitions.len() {
+                let mut visited = vec![false; transitions.len()];
+                if !augment(condition, &satisfies, &mut assigned, &mut visited) {
+                    return None;
+                }
+            }
+            matched.resize(self.transitions.len(), 0);
+            for (index, condition) in assigned.into_iter().enumerate() {
+                if let Some(condition) = condition {
+                    matched[condition] = index;
+                }
+            }
+            Some(matched)
+        } else {
+            None
+        }
+    }
+}
+
+/// Give the condition a transition it is satisfied by, either a free one or one taken from
+/// another condition that can be given a different transition. `assigned` holds the condition
+/// each transition is given to, and `visited` the transitions already tried for this search.
+fn augment(
+    condition: usize,
+    satisfies: &[Vec<bool>],
+    assigned: &mut [Option<usize>],
+    visited: &mut [bool],
+) -> bool {
+    for index in 0..assigned.len() {
+        if visited[index] || !satisfies[condition][index] {
+            continue;
+        }
+        visited[index] = true;
+        let available = match assigned[index] {
+            None => true,
+            Some(other) => augment(other, satisfies, assigned, visited),
+        };
+        if available {
+            assigned[index] = Some(condition);
+            return true;
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monitor::test_helpers::{sample_ledger, unspent_records, CurrentNetwork};
+    use crate::NamePattern;
+    use serde_json::json;
+    use snarkvm::prelude::{Address, PrivateKey, TestRng, Value};
+    use std::str::FromStr;
+
+    #[test]
+    fn test_match_transitions() {
+        let rng = &mut TestRng::default();
+        let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let ledger = sample_ledger(&private_key, rng);
+
+        // A public transfer with its fee paid by a record holds a `transfer_public` followed by
+        // a `fee_private` transition.
+        let receiver = Address::try_from(&PrivateKey::new(rng).unwrap()).unwrap();
+        let inputs = [
+            Value::from_str(&receiver.to_string()).unwrap(),
+            Value::from_str("1000u64").unwrap(),
+        ];
+        let transaction = ledger
+            .vm()
+            .execute(
+                &private_key,
+                ("credits.aleo", "transfer_public"),
+                inputs.into_iter(),
+                unspent_records(&ledger, &private_key).pop(),
+                0,
+                None,
+                rng,
+            )
+            .unwrap();
+        let transitions = transaction.transitions().collect::<Vec<_>>();
+        assert_eq!(transitions.len(), 2);
+
+        let filter = |conditions: serde_json::Value, ordered: bool| {
+            let filter = CompositeFilter::<CurrentNetwork> {
+                transitions: serde_json::from_value(conditions).unwrap(),
+                ordered,
+            };
+            filter.check().unwrap();
+            filter
+        };
+
+        // Every condition is matched, in any order unless the filter is ordered.
+        let conditions = json!([{"Function": "fee_private"}, {"Function": "transfer_public"}]);
+        assert_eq!(
+            filter(conditions.clone(), false).match_transitions(&transitions),
+            Some(vec![1, 0])
+        );
+        assert_eq!(
+            filter(conditions, true).match_transitions(&transitions),
+            None
+        );
+        let conditions = json!([{"Function": "transfer_public"}, {"Function": "fee_private"}]);
+        assert_eq!(
+            filter(conditions, true).match_transitions(&transitions),
+            Some(vec![0, 1])
+        );
+
+        // A partial match is no match.
+        let conditions = json!([{"Function": "transfer_public"}, {"Function": "fee_public"}]);
+        assert_eq!(
+            filter(conditions, false).match_transitions(&transitions),
+            None
+        );
+        let conditions = json!([{"Function": "transfer_public"}, {"Function": "transfer_public"}]);
+        assert_eq!(
+            filter(conditions, false).match_transitions(&transitions),
+            None
+        );
+
+        // The transfer satisfies both conditions. Giving it to the first would leave the second
+        // unmatched, so the first gets the fee.
+        let conditions = json!([{"Program": "credits.aleo"}, {"Function": "transfer_public"}]);
+        assert_eq!(
+            filter(conditions, false).match_transitions(&transitions),
+            Some(vec![1, 0])
+        );
+
+        // More conditions than a transaction holds transitions are rejected.
+        let conditions =
+            vec![Condition::Program(NamePattern::from_str("credits.aleo").unwrap()); 33];
+        let filter = CompositeFilter::<CurrentNetwork> {
+            transitions: conditions,
+            ordered: false,
+        };
+        assert!(filter.check().is_err());
+    }
+}
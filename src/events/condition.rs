@@ -0,0 +1,80 @@
+use super::{NamePattern, Predicate, Selector};
+use snarkvm::ledger::block::Transition;
+use snarkvm::prelude::Network;
+
+use anyhow::{anyhow, ensure, Result};
+use serde::{Deserialize, Serialize};
+
+/// A boolean expression over a transition. Leaves match the program or function name against a
+/// pattern, or test a value at a selector path; `All`, `Any` and `Not` combine them.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(bound(serialize = "N: Serialize", deserialize = "N: for<'a> Deserialize<'a>"))]
+pub enum Condition<N: Network> {
+    All(Vec<Condition<N>>),
+    Any(Vec<Condition<N>>),
+    Not(Box<Condition<N>>),
+    Program(NamePattern),
+    Function(NamePattern),
+    Value(Selector<N>, Predicate<N>),
+}
+
+impl<N: Network> Condition<N> {
+    /// Check that every pattern and predicate in the tree is valid and no `Any` node is empty.
+    pub fn check(&self) -> Result<()> {
+        match self {
+            Self::All(conditions) => conditions.iter().try_for_each(Self::check),
+            Self::Any(conditions) => {
+                ensure!(!conditions.is_empty(), "'Any' condition has no branches");
+                conditions.iter().try_for_each(Self::check)
+            }
+            Self::Not(condition) => condition.check(),
+            Self::Program(pattern) | Self::Function(pattern) => pattern.check(),
+            Self::Value(selector, predicate) => {
+                predicate.check().map_err(|e| anyhow!("{selector}: {e}"))
+            }
+        }
+    }
+
+    /// Evaluate the condition against a transition.
+    pub fn evaluate(&self, transition: &Transition<N>) -> bool {
+        match self {
+            Self::All(conditions) => conditions.iter().all(|c| c.evaluate(transition)),
+            Self::Any(conditions) => conditions.iter().any(|c| c.evaluate(transition)),
+            Self::Not(condition) => !condition.evaluate(transition),
+            Self::Program(program) => program.matches(transition.program_id()),
+            Self::Function(function) => function.matches(transition.function_name()),
+            Self::Value(selector, predicate) => selector
+                .select(transition)
+                .is_some_and(|plaintext| predicate.evaluate(plaintext)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::prelude::MainnetV0;
+
+    type CurrentNetwork = MainnetV0;
+
+    #[test]
+    fn test_condition_serde() {
+        let condition = serde_json::from_str::<Condition<CurrentNetwork>>(
+            r#"{"Any": [
+                {"All": [{"Function": "transfer_public"}, {"Value": ["input[0]", {"Eq": "1field"}]}]},
+                {"All": [{"Function": "transfer_private_to_public"}, {"Not": {"Value": ["input[1]", {"Gt": "5u64"}]}}]}
+            ]}"#,
+        )
+        .unwrap();
+        assert!(condition.check().is_ok());
+
+        let json = serde_json::to_string(&condition).unwrap();
+        assert_eq!(
+            serde_json::from_str::<Condition<CurrentNetwork>>(&json).unwrap(),
+            condition
+        );
+
+        let empty = Condition::<CurrentNetwork>::Not(Box::new(Condition::Any(vec![])));
+        assert!(empty.check().is_err());
+    }
+}
@@ -0,0 +1,132 @@
+use super::{input_plaintext, Selector};
+use snarkvm::ledger::block::Transition;
+use snarkvm::prelude::{
+    Identifier, LiteralType, Network, Plaintext, PlaintextType, ProgramID, ValueType,
+};
+use snarkvm::synthesizer::program::Program;
+
+use anyhow::{anyhow, ensure, Result};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Largest decision program a manifest can declare, in bytes of source.
+pub const MAX_DECISION_PROGRAM_SIZE: usize = 16 * 1024;
+
+/// Most decision programs a subscription can declare. Each is loaded into the subscription's
+/// process and kept for as long as the subscription.
+pub const MAX_DECISION_PROGRAMS: usize = 4;
+
+/// An Aleo function that decides whether a matched transition is reported. The program is
+/// loaded into a scratch process of the subscription and the function is evaluated off-chain with
+/// public values of the transition as inputs. The transition is reported only if the function
+/// returns `true`.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(bound(serialize = "N: Serialize", deserialize = "N: for<'a> Deserialize<'a>"))]
+pub struct DecisionFunction<N: Network> {
+    // Program source, in Aleo instructions.
+    pub program: Program<N>,
+    // Function evaluated.
+    pub function: Identifier<N>,
+    // Values passed as the function's inputs. The transition's inputs are passed in order if
+    // not set.
+    pub arguments: Option<Vec<Selector<N>>>,
+    // Whether to prove each decision that reports a transition.
+    #[serde(default)]
+    pub prove: bool,
+}
+
+impl<N: Network> DecisionFunction<N> {
+    /// Check that the program is small enough and imports nothing but `credits.aleo`, and that
+    /// the function exists, returns a single boolean and has no finalize block. Proven decisions
+    /// must also have public inputs and output.
+    pub fn check(&self) -> Result<()> {
+        let program_id = self.program.id();
+        let size = self.program.to_string().len();
+        ensure!(
+            size <= MAX_DECISION_PROGRAM_SIZE,
+            "Decision program {program_id} is {size} bytes, more than the {} allowed",
+            MAX_DECISION_PROGRAM_SIZE
+        );
+        let credits = ProgramID::from_str("credits.aleo")?;
+        ensure!(
+            *program_id != credits,
+            "Decision program cannot be named {credits}"
+        );
+        ensure!(
+            self.program
+                .imports()
+                .keys()
+                .all(|import| *import == credits),
+            "Decision program {program_id} can only import {credits}"
+        );
+        let function = self
+            .program
+            .functions()
+            .get(&self.function)
+            .ok_or_else(|| {
+                anyhow!(
+                    "Decision function {program_id}/{} does not exist",
+                    self.function
+                )
+            })?;
+        ensure!(
+            function.finalize_logic().is_none(),
+            "Decision function {program_id}/{} cannot have a finalize block",
+            self.function
+        );
+        let boolean = PlaintextType::Literal(LiteralType::Boolean);
+        let returns_boolean = match function
+            .outputs()
+            .iter()
+            .map(|output| output.value_type())
+            .collect::<Vec<_>>()[..]
+        {
+            [ValueType::Constant(plaintext_type)
+            | ValueType::Public(plaintext_type)
+            | ValueType::Private(plaintext_type)] => *plaintext_type == boolean,
+            _ => false,
+        };
+        ensure!(
+            returns_boolean,
+            "Decision function {program_id}/{} must return a single boolean",
+            self.function
+        );
+        // A proof only shows what was decided if the inputs and result are public.
+        if self.prove {
+            ensure!(
+                function
+                    .inputs()
+                    .iter()
+                    .all(|input| matches!(input.value_type(), ValueType::Public(_)))
+                    && function
+                        .outputs()
+                        .iter()
+                        .all(|output| matches!(output.value_type(), ValueType::Public(_))),
+                "Proven decision function {program_id}/{} must have public inputs and output",
+                self.function
+            );
+        }
+        if let Some(arguments) = &self.arguments {
+            ensure!(
+                arguments.len() == function.inputs().len(),
+                "Decision function {program_id}/{} takes {} inputs but {} arguments are given",
+                self.function,
+                function.inputs().len(),
+                arguments.len()
+            );
+        }
+        Ok(())
+    }
+
+    /// Returns the function's inputs for the transition, or `None` if a value is missing or not
+    /// public.
+    pub fn inputs<'a>(&self, transition: &'a Transition<N>) -> Option<Vec<&'a Plaintext<N>>> {
+        match &self.arguments {
+            Some(arguments) => arguments
+                .iter()
+                .map(|selector| selector.select(transition))
+                .collect(),
+            None => transition.inputs().iter().map(input_plaintext).collect(),
+        }
+    }
+}
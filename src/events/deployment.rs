@@ -0,0 +1,41 @@
+use snarkvm::prelude::{Identifier, Network, ProgramID};
+
+use anyhow::{ensure, Result};
+use serde::{Deserialize, Serialize};
+
+/// Selects program deployments by what the deployed program imports and declares. The program
+/// name is matched against the manifest's program pattern.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(bound(serialize = "N: Serialize", deserialize = "N: for<'a> Deserialize<'a>"))]
+pub struct DeploymentFilter<N: Network> {
+    // Programs the deployed program must import.
+    pub imports: Option<Vec<ProgramID<N>>>,
+    // Functions the deployed program must declare.
+    pub functions: Option<Vec<Identifier<N>>>,
+}
+
+impl<N: Network> DeploymentFilter<N> {
+    /// Check that the import and function lists are not empty.
+    pub fn check(&self) -> Result<()> {
+        if let Some(imports) = &self.imports {
+            ensure!(!imports.is_empty(), "Deployment import list is empty");
+        }
+        if let Some(functions) = &self.functions {
+            ensure!(!functions.is_empty(), "Deployment function list is empty");
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if the program imports and declares everything the filter lists.
+    pub fn matches(&self, imports: &[ProgramID<N>], functions: &[Identifier<N>]) -> bool {
+        self.imports
+            .iter()
+            .flatten()
+            .all(|import| imports.contains(import))
+            && self
+                .functions
+                .iter()
+                .flatten()
+                .all(|function| functions.contains(function))
+    }
+}
@@ -0,0 +1,54 @@
+use super::Predicate;
+use crate::FeeDetails;
+use snarkvm::prelude::{Literal, LiteralType, Network, Plaintext, U64};
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+/// Conditions on the fee a transaction paid. Operands are `u64` microcredit amounts.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(bound(serialize = "N: Serialize", deserialize = "N: for<'a> Deserialize<'a>"))]
+pub struct FeeFilter<N: Network> {
+    pub base: Option<Predicate<N>>,
+    pub priority: Option<Predicate<N>>,
+    pub total: Option<Predicate<N>>,
+}
+
+impl<N: Network> FeeFilter<N> {
+    /// Check that every predicate is well typed and compares `u64` amounts.
+    pub fn check(&self) -> Result<()> {
+        for (name, predicate) in self.predicates() {
+            if let Some(predicate) = predicate {
+                predicate
+                    .check()
+                    .and_then(|_| predicate.check_literal_type(LiteralType::U64))
+                    .map_err(|e| anyhow!("{name} fee: {e}"))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if the transaction paid a fee satisfying every predicate.
+    pub fn matches(&self, fee: Option<&FeeDetails>) -> bool {
+        let Some(fee) = fee else {
+            return false;
+        };
+        let amounts = [fee.base_amount(), fee.priority_amount(), fee.total_amount()];
+        self.predicates()
+            .into_iter()
+            .zip(amounts)
+            .all(|((_, predicate), amount)| {
+                predicate.is_none_or(|predicate| {
+                    predicate.evaluate(&Plaintext::from(Literal::U64(U64::new(amount))))
+                })
+            })
+    }
+
+    fn predicates(&self) -> [(&'static str, Option<&Predicate<N>>); 3] {
+        [
+            ("base", self.base.as_ref()),
+            ("priority", self.priority.as_ref()),
+            ("total", self.total.as_ref()),
+        ]
+    }
+}
@@ -0,0 +1,50 @@
+use super::Predicate;
+use snarkvm::prelude::{Identifier, Network, ProgramID};
+
+use anyhow::{anyhow, ensure, Result};
+use serde::{Deserialize, Serialize};
+
+/// A change to a mapping entry made in finalize.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum MappingOperation {
+    Insert,
+    Update,
+    Remove,
+}
+
+/// Selects finalize updates to one mapping of a program, e.g. `credits.aleo/account`.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(bound(serialize = "N: Serialize", deserialize = "N: for<'a> Deserialize<'a>"))]
+pub struct MappingFilter<N: Network> {
+    pub program: ProgramID<N>,
+    pub mapping: Identifier<N>,
+    // The operations to report, all of them if unset.
+    pub operations: Option<Vec<MappingOperation>>,
+    // A condition on the key. Keys listed by `Eq` or `In` are always recoverable.
+    pub key: Option<Predicate<N>>,
+    // A condition on the value after the update.
+    pub value: Option<Predicate<N>>,
+}
+
+impl<N: Network> MappingFilter<N> {
+    /// Check the operation list and the key and value predicates.
+    pub fn check(&self) -> Result<()> {
+        if let Some(operations) = &self.operations {
+            ensure!(!operations.is_empty(), "Mapping operation list is empty");
+        }
+        if let Some(key) = &self.key {
+            key.check().map_err(|e| anyhow!("mapping key: {e}"))?;
+        }
+        if let Some(value) = &self.value {
+            value.check().map_err(|e| anyhow!("mapping value: {e}"))?;
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if the operation should be reported.
+    pub fn includes(&self, operation: MappingOperation) -> bool {
+        self.operations
+            .as_ref()
+            .is_none_or(|operations| operations.contains(&operation))
+    }
+}
@@ -0,0 +1,810 @@
+use crate::action::ChainAction;
+use crate::FeeDetails;
+use snarkvm::ledger::block::Transition;
+use snarkvm::prelude::{GraphKey, Network, Plaintext, ViewKey};
+
+use anyhow::{ensure, Result};
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Debug};
+
+mod abi;
+
+pub mod aggregate;
+pub use aggregate::*;
+
+pub mod block;
+pub use block::*;
+
+pub mod committee;
+pub use committee::*;
+
+pub mod composite;
+pub use composite::*;
+
+pub mod condition;
+pub use condition::*;
+
+pub mod decision;
+pub use decision::*;
+
+pub mod deployment;
+pub use deployment::*;
+
+pub mod fee;
+pub use fee::*;
+
+pub mod mapping;
+pub use mapping::*;
+
+pub mod pattern;
+pub use pattern::*;
+
+pub mod predicate;
+pub use predicate::*;
+
+pub mod selector;
+pub use selector::*;
+
+pub mod sequence;
+pub use sequence::*;
+
+pub mod spent;
+pub use spent::*;
+
+pub mod status;
+pub use status::*;
+
+/// The kind of on-chain activity a manifest watches.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(bound(serialize = "N: Serialize", deserialize = "N: for<'a> Deserialize<'a>"))]
+pub enum EventKind<N: Network> {
+    // Function calls, selected by the manifest's program, function and value filters.
+    Transition,
+    // Finalize updates to a mapping.
+    Mapping(MappingFilter<N>),
+    // Program deployments, selected by the manifest's program pattern and the filter.
+    Deployment(DeploymentFilter<N>),
+    // Records owned by the subscription's view key, output by transitions the manifest matches.
+    Record,
+    // Watched records spent by transitions the manifest matches.
+    RecordSpent(SpentFilter<N>),
+    // Transactions holding a transition for each of several conditions.
+    Composite(CompositeFilter<N>),
+    // Transitions matching a series of conditions in order across blocks.
+    Sequence(SequenceFilter<N>),
+    // An aggregate over a window of blocks of a value of the transitions the manifest matches.
+    Aggregate(AggregateFilter<N>),
+    // Blocks, selected by their header and contents.
+    Block(BlockFilter<N>),
+    // Changes to the validator committee between a block and the one before it.
+    Committee(CommitteeFilter<N>),
+    // Transactions the block aborted. The ledger keeps only their IDs, so they cannot be
+    // filtered.
+    Aborted,
+}
+
+impl<N: Network> Default for EventKind<N> {
+    fn default() -> Self {
+        Self::Transition
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(bound(serialize = "N: Serialize", deserialize = "N: for<'a> Deserialize<'a>"))]
+pub struct EventManifest<N: Network> {
+    pub name: String,
+    pub description: String,
+    #[serde(default)]
+    pub kind: EventKind<N>,
+    #[serde(default)]
+    pub status: StatusFilter,
+    pub fee: Option<FeeFilter<N>>,
+    pub function: Option<NamePattern>,
+    pub program: Option<NamePattern>,
+    pub inputs: Option<IndexMap<usize, Predicate<N>>>,
+    pub outputs: Option<IndexMap<usize, Predicate<N>>>,
+    pub selectors: Option<IndexMap<Selector<N>, Predicate<N>>>,
+    pub condition: Option<Condition<N>>,
+    pub decision: Option<DecisionFunction<N>>,
+    #[serde(default)]
+    pub inclusion_proof: bool,
+    pub actions: Vec<ChainAction>,
+}
+
+impl<N: Network> EventManifest<N> {
+    /// Check that every name pattern is valid and every predicate is well typed.
+    pub fn check(&self) -> Result<()> {
+        let problems = self.problems();
+        ensure!(problems.is_empty(), "{}", problems.join("; "));
+        Ok(())
+    }
+
+    /// Check the manifest and return every problem found.
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems = vec![];
+        let name = &self.name;
+        for pattern in self.program.iter().chain(self.function.iter()) {
+            if let Err(e) = pattern.check() {
+                problems.push(format!("Event '{name}': {e}"));
+            }
+        }
+        for (index, predicate) in self.inputs.iter().flatten() {
+            if let Err(e) = predicate.check() {
+                problems.push(format!("Event '{name}' input {index}: {e}"));
+            }
+        }
+        for (index, predicate) in self.outputs.iter().flatten() {
+            if let Err(e) = predicate.check() {
+                problems.push(format!("Event '{name}' output {index}: {e}"));
+            }
+        }
+        for (selector, predicate) in self.selectors.iter().flatten() {
+            if let Err(e) = predicate.check() {
+                problems.push(format!("Event '{name}' {selector}: {e}"));
+            }
+        }
+        if let Some(condition) = &self.condition {
+            if let Err(e) = condition.check() {
+                problems.push(format!("Event '{name}' condition: {e}"));
+            }
+        }
+        if let Some(decision) = &self.decision {
+            if let Err(e) = decision.check() {
+                problems.push(format!("Event '{name}' {e}"));
+            }
+        }
+        if let Some(fee) = &self.fee {
+            if let Err(e) = fee.check() {
+                problems.push(format!("Event '{name}' {e}"));
+            }
+        }
+        for action in &self.actions {
+            if let Err(e) = action.check() {
+                problems.push(format!("Event '{name}' {e}"));
+            }
+        }
+        let kind = match &self.kind {
+            EventKind::Transition => self.check_has_criteria(),
+            EventKind::Record | EventKind::RecordSpent(_) => Ok(()),
+            EventKind::Mapping(filter) => filter
+                .check()
+                .and_then(|()| self.check_no_transition_filters()),
+            EventKind::Deployment(filter) => filter
+                .check()
+                .and_then(|()| self.check_no_function_or_values()),
+            EventKind::Composite(filter) => filter
+                .check()
+                .and_then(|()| self.check_selected_by_conditions()),
+            EventKind::Sequence(filter) => filter
+                .check()
+                .and_then(|()| self.check_selected_by_conditions()),
+            EventKind::Aggregate(filter) => filter.check(),
+            EventKind::Block(filter) => filter
+                .check()
+                .and_then(|()| self.check_no_transition_filters()),
+            EventKind::Committee(filter) => filter
+                .check()
+                .and_then(|()| self.check_no_transition_filters()),
+            EventKind::Aborted => self.check_unfiltered(),
+        }
+        .and_then(|()| self.check_inclusion_proof());
+        if let Err(e) = kind {
+            problems.push(format!("Event '{name}' {e}"));
+        }
+        problems
+    }
+
+    /// Ensure a transition manifest does not match every transition.
+    fn check_has_criteria(&self) -> Result<()> {
+        ensure!(
+            self.program.is_some()
+                || self.function.is_some()
+                || self.inputs.is_some()
+                || self.outputs.is_some()
+                || self.selectors.is_some()
+                || self.condition.is_some(),
+            "would match every transition; set a program, function, inputs, outputs, selectors \
+             or condition"
+        );
+        Ok(())
+    }
+
+    /// Ensure a composite or sequence manifest sets no transition filters of its own, since only
+    /// the conditions of its filter select transitions.
+    fn check_selected_by_conditions(&self) -> Result<()> {
+        ensure!(
+            self.program.is_none()
+                && self.function.is_none()
+                && self.inputs.is_none()
+                && self.outputs.is_none()
+                && self.selectors.is_none()
+                && self.condition.is_none()
+                && self.decision.is_none(),
+            "selects transitions by the conditions of its filter; set the program, function and \
+             values there, and remove the program, function, inputs, outputs, selectors, \
+             condition and decision of the manifest"
+        );
+        Ok(())
+    }
+
+    /// Ensure a manifest that does not watch transitions sets no transition filters, since they
+    /// would be ignored.
+    fn check_no_transition_filters(&self) -> Result<()> {
+        ensure!(
+            self.program.is_none(),
+            "does not watch transitions; remove the program of the manifest"
+        );
+        self.check_no_function_or_values()
+    }
+
+    /// Ensure a manifest sets no function or transition value filters. Deployment manifests
+    /// match their program pattern against the deployed program, so only that is allowed.
+    fn check_no_function_or_values(&self) -> Result<()> {
+        ensure!(
+            self.function.is_none()
+                && self.inputs.is_none()
+                && self.outputs.is_none()
+                && self.selectors.is_none()
+                && self.condition.is_none()
+                && self.decision.is_none(),
+            "does not watch transitions; remove the function, inputs, outputs, selectors, \
+             condition and decision of the manifest"
+        );
+        Ok(())
+    }
+
+    /// Ensure inclusion proofs are only asked of kinds that report a transition, since no other
+    /// event has one to prove included.
+    fn check_inclusion_proof(&self) -> Result<()> {
+        ensure!(
+            !self.inclusion_proof
+                || matches!(
+                    self.kind,
+                    EventKind::Transition | EventKind::Record | EventKind::RecordSpent(_)
+                ),
+            "has no transition to prove included; remove the inclusion proof of the manifest"
+        );
+        Ok(())
+    }
+
+    /// Ensure a manifest of aborted transactions sets no filters, since they cannot be applied.
+    fn check_unfiltered(&self) -> Result<()> {
+        ensure!(
+            self.program.is_none()
+                && self.function.is_none()
+                && self.inputs.is_none()
+                && self.outputs.is_none()
+                && self.selectors.is_none()
+                && self.condition.is_none()
+                && self.decision.is_none()
+                && self.fee.is_none(),
+            "watches aborted transactions, which cannot be filtered"
+        );
+        Ok(())
+    }
+
+    /// Returns `true` if the transition is a call to a program and function matching the
+    /// manifest's patterns, the public value at every listed input and output index and at every
+    /// selector path satisfies its predicate, and the condition tree holds. A missing program or
+    /// function pattern matches any.
+    pub fn matches(&self, transition: &Transition<N>) -> bool {
+        self.program
+            .as_ref()
+            .is_none_or(|program| program.matches(transition.program_id()))
+            && self
+                .function
+                .as_ref()
+                .is_none_or(|function| function.matches(transition.function_name()))
+            && Self::matches_values(self.inputs.as_ref(), transition.inputs(), input_plaintext)
+            && Self::matches_values(
+                self.outputs.as_ref(),
+                transition.outputs(),
+                output_plaintext,
+            )
+            && self
+                .selectors
+                .iter()
+                .flatten()
+                .all(|(selector, predicate)| {
+                    selector
+                        .select(transition)
+                        .is_some_and(|plaintext| predicate.evaluate(plaintext))
+                })
+            && self
+                .condition
+                .as_ref()
+                .is_none_or(|condition| condition.evaluate(transition))
+    }
+
+    /// Returns `true` if there is no fee filter or the fee satisfies it.
+    pub fn matches_fee(&self, fee: Option<&FeeDetails>) -> bool {
+        self.fee.as_ref().is_none_or(|filter| filter.matches(fee))
+    }
+
+    /// Evaluate each predicate against the public plaintext at its index. Private, record and
+    /// missing values never match.
+    fn matches_values<T>(
+        predicates: Option<&IndexMap<usize, Predicate<N>>>,
+        values: &[T],
+        to_plaintext: fn(&T) -> Option<&Plaintext<N>>,
+    ) -> bool {
+        predicates.is_none_or(|predicates| {
+            predicates.iter().all(|(index, predicate)| {
+                values
+                    .get(*index)
+                    .and_then(to_plaintext)
+                    .is_some_and(|plaintext| predicate.evaluate(plaintext))
+            })
+        })
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(bound(serialize = "N: Serialize", deserialize = "N: for<'a> Deserialize<'a>"))]
+pub struct EventManifests<N: Network> {
+    manifests: Vec<EventManifest<N>>,
+    // View key used to decrypt records for `Record` manifests.
+    #[serde(default)]
+    view_key: Option<ViewKey<N>>,
+    // Graph key used to derive record tags for `RecordSpent` manifests.
+    #[serde(default)]
+    graph_key: Option<GraphKey<N>>,
+    // Whether to re-verify the transactions of matched events before reporting them.
+    #[serde(default)]
+    paranoid: bool,
+}
+
+impl<N: Network> EventManifests<N> {
+    pub fn new(manifests: Vec<EventManifest<N>>) -> EventManifests<N> {
+        Self {
+            manifests,
+            view_key: None,
+            graph_key: None,
+            paranoid: false,
+        }
+    }
+
+    /// Set the view key used to decrypt records.
+    pub fn with_view_key(mut self, view_key: ViewKey<N>) -> EventManifests<N> {
+        self.view_key = Some(view_key);
+        self
+    }
+
+    /// Set the graph key used to detect spent records.
+    pub fn with_graph_key(mut self, graph_key: GraphKey<N>) -> EventManifests<N> {
+        self.graph_key = Some(graph_key);
+        self
+    }
+
+    /// Set whether to re-verify the transactions of matched events before reporting them.
+    pub fn with_paranoid(mut self, paranoid: bool) -> EventManifests<N> {
+        self.paranoid = paranoid;
+        self
+    }
+
+    pub fn manifests(&self) -> &Vec<EventManifest<N>> {
+        &self.manifests
+    }
+
+    /// Get the view key used to decrypt records.
+    pub fn view_key(&self) -> Option<&ViewKey<N>> {
+        self.view_key.as_ref()
+    }
+
+    /// Returns `true` if matched transactions are re-verified before events are reported.
+    pub fn is_paranoid(&self) -> bool {
+        self.paranoid
+    }
+
+    /// Get the graph key used to detect spent records, derived from the view key if not set.
+    pub fn graph_key(&self) -> Option<GraphKey<N>> {
+        self.graph_key.or_else(|| {
+            self.view_key
+                .and_then(|view_key| GraphKey::try_from(view_key).ok())
+        })
+    }
+
+    /// Check every manifest in the set.
+    pub fn check(&self) -> Result<()> {
+        let problems = self.problems();
+        ensure!(problems.is_empty(), "{}", problems.join("; "));
+        Ok(())
+    }
+
+    /// Check every manifest in the set and return every problem found. Names must be unique,
+    /// since watched records, sequences and aggregate windows are kept per event name.
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems = self
+            .manifests
+            .iter()
+            .flat_map(|manifest| self.manifest_problems(manifest))
+            .collect::<Vec<_>>();
+        let mut names = IndexMap::<&str, usize>::new();
+        for manifest in &self.manifests {
+            *names.entry(&manifest.name).or_default() += 1;
+        }
+        for (name, count) in names {
+            if count > 1 {
+                problems.push(format!("Event '{name}' is declared {count} times"));
+            }
+        }
+        // Decision programs are loaded into one process per subscription, keyed by name.
+        let mut programs = IndexMap::new();
+        for manifest in &self.manifests {
+            let Some(decision) = &manifest.decision else {
+                continue;
+            };
+            let program = programs
+                .entry(*decision.program.id())
+                .or_insert(&decision.program);
+            if *program != &decision.program {
+                problems.push(format!(
+                    "Event '{}' declares a decision program {} that differs from another of the \
+                     same name",
+                    manifest.name,
+                    decision.program.id()
+                ));
+            }
+        }
+        if programs.len() > MAX_DECISION_PROGRAMS {
+            problems.push(format!(
+                "The subscription declares {} decision programs, more than the {} allowed",
+                programs.len(),
+                MAX_DECISION_PROGRAMS
+            ));
+        }
+        problems
+    }
+
+    /// Check a manifest and that the subscription has the keys it needs.
+    fn manifest_problems(&self, manifest: &EventManifest<N>) -> Vec<String> {
+        let mut problems = manifest.problems();
+        if matches!(manifest.kind, EventKind::Record) && self.view_key.is_none() {
+            problems.push(format!(
+                "Event '{}' watches records but the subscription has no view key",
+                manifest.name
+            ));
+        }
+        if matches!(manifest.kind, EventKind::RecordSpent(_)) && self.graph_key().is_none() {
+            problems.push(format!(
+                "Event '{}' watches spent records but the subscription has no graph or view key",
+                manifest.name
+            ));
+        }
+        problems
+    }
+}
+
+// The view and graph keys are left out so they never reach the logs.
+impl<N: Network> Debug for EventManifests<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("EventManifests")
+            .field("manifests", &self.manifests)
+            .field("view_key", &self.view_key.as_ref().map(|_| "<redacted>"))
+            .field("graph_key", &self.graph_key.as_ref().map(|_| "<redacted>"))
+            .field("paranoid", &self.paranoid)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::prelude::{Literal, MainnetV0};
+    use snarkvm::synthesizer::program::Program;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_item() {
+        let manifest = EventManifest {
+            name: "transferPublics".to_string(),
+            description: "Find transfer publics".to_string(),
+            kind: EventKind::Transition,
+            status: StatusFilter::Accepted,
+            fee: None,
+            function: Some(NamePattern::from_str("transfer_public").unwrap()),
+            program: Some(NamePattern::from_str("credits.aleo").unwrap()),
+            inputs: None,
+            outputs: None,
+            selectors: None,
+            condition: None,
+            decision: None,
+            inclusion_proof: false,
+            actions: vec![ChainAction::Notify],
+        };
+        let json_manifest = serde_json::to_string(&manifest).unwrap();
+
+        println!("{json_manifest:?}");
+        let manifests = EventManifests::<MainnetV0>::new(vec![manifest]);
+        let manifests = serde_json::to_string(&manifests).unwrap();
+        println!("{manifests:?}");
+    }
+
+    #[test]
+    fn test_plaintext_filters() {
+        // Manifests written before predicates were added compare values for equality.
+        let manifest: EventManifest<MainnetV0> = serde_json::from_value(serde_json::json!({
+            "name": "transferPublics",
+            "description": "Find transfer publics",
+            "program": "credits.aleo",
+            "function": "transfer_public",
+            "inputs": {"1": "1000000u64"},
+            "outputs": {"0": {"Gt": "5u64"}},
+            "actions": ["Notify"],
+        }))
+        .unwrap();
+        manifest.check().unwrap();
+        assert_eq!(
+            manifest.inputs.unwrap()[&1],
+            Predicate::Eq(Plaintext::from_str("1000000u64").unwrap())
+        );
+        assert_eq!(
+            manifest.outputs.unwrap()[&0],
+            Predicate::Gt(Literal::from_str("5u64").unwrap())
+        );
+    }
+
+    #[test]
+    fn test_unfiltered_transitions() {
+        let manifest: EventManifest<MainnetV0> = serde_json::from_value(serde_json::json!({
+            "name": "everything",
+            "description": "Every transition",
+            "actions": ["Notify"],
+        }))
+        .unwrap();
+        assert!(manifest.check().is_err());
+
+        let mut manifest = manifest;
+        manifest.function = Some(NamePattern::from_str("transfer_*").unwrap());
+        manifest.check().unwrap();
+    }
+
+    #[test]
+    fn test_problems() {
+        let manifest: EventManifest<MainnetV0> = serde_json::from_value(serde_json::json!({
+            "name": "broken",
+            "description": "Several mistakes",
+            "program": "credits.aleo",
+            "inputs": {"0": {"Gt": "true"}},
+            "outputs": {"1": {"In": []}},
+            "actions": [{"Webhook": {"url": "ftp://example.com", "secret": "s"}}],
+        }))
+        .unwrap();
+        let records: EventManifest<MainnetV0> = serde_json::from_value(serde_json::json!({
+            "name": "records",
+            "description": "Records without a view key",
+            "kind": "Record",
+            "actions": ["Notify"],
+        }))
+        .unwrap();
+        assert_eq!(manifest.problems().len(), 3, "{:#?}", manifest.problems());
+
+        let manifests = EventManifests::new(vec![manifest, records]);
+        let problems = manifests.problems();
+        assert_eq!(problems.len(), 4, "{problems:#?}");
+        assert!(problems[0].starts_with("Event 'broken' input 0"));
+        assert!(problems[3].starts_with("Event 'records'"));
+        assert!(manifests.check().is_err());
+
+        // Names are unique within a subscription.
+        let names = serde_json::json!({
+            "name": "transfers",
+            "description": "Transfers",
+            "function": "transfer_public",
+            "actions": ["Notify"],
+        });
+        let transfers: EventManifest<MainnetV0> = serde_json::from_value(names).unwrap();
+        let manifests = EventManifests::new(vec![transfers.clone()]);
+        assert!(manifests.problems().is_empty());
+        let manifests = EventManifests::new(vec![transfers.clone(), transfers]);
+        assert_eq!(
+            manifests.problems(),
+            vec!["Event 'transfers' is declared 2 times".to_string()]
+        );
+
+        // A subscription declares a bounded number of decision programs, each under its own
+        // name.
+        let decision = |name: &str, program: &str, comparison: &str| {
+            let manifest = serde_json::json!({
+                "name": name,
+                "description": "",
+                "function": "transfer_public",
+                "decision": {
+                    "program": format!("program {program}.aleo;\n\nfunction decide:\n    input r0 as u64.public;\n    {comparison} r0 0u64 into r1;\n    output r1 as boolean.public;\n"),
+                    "function": "decide",
+                    "arguments": ["input[1]"],
+                },
+                "actions": ["Notify"],
+            });
+            serde_json::from_value::<EventManifest<MainnetV0>>(manifest).unwrap()
+        };
+        let manifests = EventManifests::new(vec![
+            decision("above", "decision_a", "gt"),
+            decision("again", "decision_a", "gt"),
+        ]);
+        assert!(manifests.problems().is_empty());
+        let manifests = EventManifests::new(vec![
+            decision("above", "decision_a", "gt"),
+            decision("below", "decision_a", "lt"),
+        ]);
+        let problems = manifests.problems();
+        assert_eq!(problems.len(), 1, "{problems:#?}");
+        assert!(problems[0].starts_with("Event 'below' declares a decision program"));
+        let manifests = EventManifests::new(
+            (0..=MAX_DECISION_PROGRAMS)
+                .map(|i| decision(&format!("event{i}"), &format!("decision_{i}"), "gt"))
+                .collect(),
+        );
+        let problems = manifests.problems();
+        assert_eq!(problems.len(), 1, "{problems:#?}");
+        assert!(problems[0].starts_with("The subscription declares 5 decision programs"));
+
+        // Each program is bounded in size.
+        let instructions = (2..700)
+            .map(|register| format!("    add r0 1u64 into r{register};\n"))
+            .collect::<String>();
+        let mut large = decision("large", "decision_large", "gt");
+        large.decision.as_mut().unwrap().program = Program::from_str(&format!(
+            "program decision_large.aleo;\n\nfunction decide:\n    input r0 as u64.public;\n{instructions}    gt r0 0u64 into r1;\n    output r1 as boolean.public;\n"
+        ))
+        .unwrap();
+        let problems = large.problems();
+        assert_eq!(problems.len(), 1, "{problems:#?}");
+        assert!(problems[0].contains("more than the 16384 allowed"));
+    }
+
+    #[test]
+    fn test_condition_kinds_reject_transition_filters() {
+        let manifest: EventManifest<MainnetV0> = serde_json::from_value(serde_json::json!({
+            "name": "unbondAndClaim",
+            "description": "An unbond followed by a claim",
+            "kind": {"Sequence": {
+                "steps": [
+                    {"Function": "unbond_public"},
+                    {"Function": "claim_unbond_public"},
+                ],
+                "within": 400,
+            }},
+            "actions": ["Notify"],
+        }))
+        .unwrap();
+        manifest.check().unwrap();
+
+        let mut filtered = manifest.clone();
+        filtered.program = Some(NamePattern::from_str("credits.aleo").unwrap());
+        assert!(filtered.check().is_err());
+
+        let mut composite = manifest;
+        composite.kind = EventKind::Composite(CompositeFilter {
+            transitions: vec![Condition::Function(
+                NamePattern::from_str("transfer_public").unwrap(),
+            )],
+            ordered: false,
+        });
+        composite.check().unwrap();
+        composite.inclusion_proof = true;
+        assert!(composite.check().is_err());
+        composite.inclusion_proof = false;
+        composite.inputs = Some(IndexMap::from([(
+            0,
+            Predicate::Eq(Plaintext::from_str("1u64").unwrap()),
+        )]));
+        assert!(composite.check().is_err());
+    }
+
+    #[test]
+    fn test_untargeted_kinds_reject_transition_filters() {
+        let manifest = |kind: serde_json::Value, field: Option<(&str, serde_json::Value)>| {
+            let mut manifest = serde_json::json!({
+                "name": "untargeted",
+                "description": "",
+                "kind": kind,
+                "actions": ["Notify"],
+            });
+            if let Some((field, value)) = field {
+                manifest[field] = value;
+            }
+            serde_json::from_value::<EventManifest<MainnetV0>>(manifest).unwrap()
+        };
+        let kinds = [
+            serde_json::json!({"Mapping": {"program": "credits.aleo", "mapping": "account"}}),
+            serde_json::json!({"Block": {"empty": true}}),
+            serde_json::json!({"Committee": {"stake_change": 1_000_000}}),
+        ];
+        for kind in kinds {
+            manifest(kind.clone(), None).check().unwrap();
+            for field in [
+                ("program", serde_json::json!("credits.aleo")),
+                ("function", serde_json::json!("transfer_public")),
+                ("inputs", serde_json::json!({"1": {"Gt": "1000u64"}})),
+                ("inclusion_proof", serde_json::json!(true)),
+            ] {
+                assert!(manifest(kind.clone(), Some(field)).check().is_err());
+            }
+        }
+
+        // Deployment manifests match the program pattern against the deployed program.
+        let deployment = serde_json::json!({"Deployment": {}});
+        let program = ("program", serde_json::json!("token_*"));
+        manifest(deployment.clone(), Some(program)).check().unwrap();
+        for field in [
+            ("function", serde_json::json!("transfer_public")),
+            ("inclusion_proof", serde_json::json!(true)),
+        ] {
+            assert!(manifest(deployment.clone(), Some(field)).check().is_err());
+        }
+
+        // Aborted transactions are left out of the block, so they cannot be proven included.
+        let aborted = serde_json::json!("Aborted");
+        manifest(aborted.clone(), None).check().unwrap();
+        let proof = ("inclusion_proof", serde_json::json!(true));
+        assert!(manifest(aborted, Some(proof)).check().is_err());
+    }
+
+    #[test]
+    fn test_matches_genesis_transitions() {
+        use crate::monitor::test_helpers::sample_ledger;
+        use serde_json::json;
+        use snarkvm::prelude::{Address, PrivateKey, TestRng};
+
+        let rng = &mut TestRng::default();
+        let private_key = PrivateKey::<MainnetV0>::new(rng).unwrap();
+        let ledger = sample_ledger(&private_key, rng);
+        let transactions = ledger.get_transactions(0).unwrap();
+        let transition = transactions
+            .iter()
+            .next()
+            .unwrap()
+            .transitions()
+            .next()
+            .unwrap()
+            .clone();
+        // `transfer_public_to_private` takes a private receiver and a public amount, and outputs
+        // a record and a future.
+        let amount = input_plaintext(&transition.inputs()[1]).unwrap().clone();
+        let matches = |inputs: serde_json::Value, outputs: serde_json::Value| {
+            let manifest: EventManifest<MainnetV0> = serde_json::from_value(json!({
+                "name": "genesisTransfers",
+                "description": "",
+                "program": "credits.aleo",
+                "function": "transfer_public_to_private",
+                "inputs": inputs,
+                "outputs": outputs,
+                "actions": ["Notify"],
+            }))
+            .unwrap();
+            manifest.matches(&transition)
+        };
+
+        assert!(matches(json!(null), json!(null)));
+        // The public amount is selected by its value.
+        assert!(matches(
+            json!({"1": {"Eq": amount.to_string()}}),
+            json!(null)
+        ));
+        assert!(matches(
+            json!({"1": {"Gte": amount.to_string()}}),
+            json!(null)
+        ));
+        assert!(!matches(
+            json!({"1": {"Gt": amount.to_string()}}),
+            json!(null)
+        ));
+        assert!(!matches(
+            json!({"1": {"Ne": amount.to_string()}}),
+            json!(null)
+        ));
+        // The private receiver, the record output and missing indices never match.
+        let receiver = Address::try_from(&private_key).unwrap().to_string();
+        assert!(!matches(json!({"0": {"Eq": receiver}}), json!(null)));
+        assert!(!matches(
+            json!(null),
+            json!({"0": {"Eq": amount.to_string()}})
+        ));
+        assert!(!matches(
+            json!({"2": {"Eq": amount.to_string()}}),
+            json!(null)
+        ));
+    }
+}
@@ -0,0 +1,158 @@
+use anyhow::{ensure, Error, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+/// A program or function name pattern. `*` matches any run of characters and `?` matches a
+/// single character, so `myorg_*.aleo` matches every program whose name starts with `myorg_`.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(transparent)]
+pub struct Glob(String);
+
+impl Glob {
+    /// Check that the pattern only contains characters valid in Aleo names plus wildcards.
+    pub fn check(&self) -> Result<()> {
+        ensure!(!self.0.is_empty(), "Name pattern is empty");
+        ensure!(
+            self.0
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '*' | '?')),
+            "Name pattern '{}' contains invalid characters",
+            self.0
+        );
+        Ok(())
+    }
+
+    /// Returns the pattern if it has no wildcards and so matches exactly one name.
+    pub fn literal(&self) -> Option<&str> {
+        (!self.0.contains(['*', '?'])).then_some(self.0.as_str())
+    }
+
+    /// Returns `true` if the name matches the pattern.
+    pub fn matches(&self, name: &str) -> bool {
+        let (pattern, name) = (self.0.as_bytes(), name.as_bytes());
+        let (mut p, mut n) = (0, 0);
+        // The position of the last `*` and the name position it is currently matched up to.
+        let mut backtrack = None;
+        while n < name.len() {
+            if p < pattern.len() && (pattern[p] == b'?' || pattern[p] == name[n]) {
+                p += 1;
+                n += 1;
+            } else if p < pattern.len() && pattern[p] == b'*' {
+                backtrack = Some((p, n));
+                p += 1;
+            } else if let Some((star, matched)) = backtrack {
+                // Let the last `*` absorb one more character and retry.
+                backtrack = Some((star, matched + 1));
+                p = star + 1;
+                n = matched + 1;
+            } else {
+                return false;
+            }
+        }
+        pattern[p..].iter().all(|c| *c == b'*')
+    }
+}
+
+impl FromStr for Glob {
+    type Err = Error;
+
+    fn from_str(pattern: &str) -> Result<Self> {
+        let glob = Self(pattern.to_string());
+        glob.check()?;
+        Ok(glob)
+    }
+}
+
+impl Display for Glob {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Selects programs or functions by one name pattern or a list of them.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum NamePattern {
+    One(Glob),
+    Many(Vec<Glob>),
+}
+
+impl NamePattern {
+    /// Check every pattern in the selection.
+    pub fn check(&self) -> Result<()> {
+        match self {
+            Self::One(glob) => glob.check(),
+            Self::Many(globs) => {
+                ensure!(!globs.is_empty(), "Name pattern list is empty");
+                globs.iter().try_for_each(Glob::check)
+            }
+        }
+    }
+
+    /// Returns the names selected if no pattern has wildcards.
+    pub fn literals(&self) -> Option<Vec<&str>> {
+        match self {
+            Self::One(glob) => Some(vec![glob.literal()?]),
+            Self::Many(globs) => globs.iter().map(Glob::literal).collect(),
+        }
+    }
+
+    /// Returns `true` if any pattern matches the name.
+    pub fn matches(&self, name: &impl ToString) -> bool {
+        let name = name.to_string();
+        match self {
+            Self::One(glob) => glob.matches(&name),
+            Self::Many(globs) => globs.iter().any(|glob| glob.matches(&name)),
+        }
+    }
+}
+
+impl FromStr for NamePattern {
+    type Err = Error;
+
+    fn from_str(pattern: &str) -> Result<Self> {
+        Ok(Self::One(Glob::from_str(pattern)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glob() {
+        let glob = Glob::from_str("myorg_*.aleo").unwrap();
+        assert!(glob.matches("myorg_token.aleo"));
+        assert!(glob.matches("myorg_.aleo"));
+        assert!(!glob.matches("credits.aleo"));
+        assert!(!glob.matches("myorg_token.aleo.aleo.x"));
+
+        assert!(Glob::from_str("*").unwrap().matches("transfer_public"));
+        assert!(Glob::from_str("transfer_*_to_*")
+            .unwrap()
+            .matches("transfer_private_to_public"));
+        assert!(Glob::from_str("bond_?ublic")
+            .unwrap()
+            .matches("bond_public"));
+        assert!(!Glob::from_str("bond_?ublic").unwrap().matches("bond_ublic"));
+        assert!(Glob::from_str("credits/aleo").is_err());
+        assert_eq!(glob.literal(), None);
+        assert_eq!(
+            Glob::from_str("credits.aleo").unwrap().literal(),
+            Some("credits.aleo")
+        );
+    }
+
+    #[test]
+    fn test_name_pattern_serde() {
+        let one: NamePattern = serde_json::from_str(r#""credits.aleo""#).unwrap();
+        assert_eq!(one, NamePattern::from_str("credits.aleo").unwrap());
+
+        let many: NamePattern =
+            serde_json::from_str(r#"["transfer_public", "transfer_private_to_public"]"#).unwrap();
+        assert!(many.matches(&"transfer_private_to_public"));
+        assert!(!many.matches(&"transfer_private"));
+        assert!(NamePattern::Many(vec![]).check().is_err());
+    }
+}
@@ -0,0 +1,235 @@
+use snarkvm::prelude::{Compare, Literal, LiteralType, Network, Plaintext};
+
+use anyhow::{bail, ensure, Result};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp::Ordering;
+
+/// A condition on a single plaintext value. Equality and set membership work on any plaintext,
+/// ordering operators work on integer and field literals of the same type.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(remote = "Self")]
+#[serde(bound(serialize = "N: Serialize", deserialize = "N: for<'a> Deserialize<'a>"))]
+pub enum Predicate<N: Network> {
+    Eq(Plaintext<N>),
+    Ne(Plaintext<N>),
+    Gt(Literal<N>),
+    Gte(Literal<N>),
+    Lt(Literal<N>),
+    Lte(Literal<N>),
+    Between(Literal<N>, Literal<N>),
+    In(Vec<Plaintext<N>>),
+}
+
+impl<N: Network> Predicate<N> {
+    /// Check that the operator supports the operand types and that all operands agree.
+    pub fn check(&self) -> Result<()> {
+        match self {
+            Self::Eq(_) | Self::Ne(_) => Ok(()),
+            Self::Gt(bound) | Self::Gte(bound) | Self::Lt(bound) | Self::Lte(bound) => {
+                ensure_ordered(bound)
+            }
+            Self::Between(low, high) => {
+                ensure_ordered(low)?;
+                match compare_literals(low, high) {
+                    Some(Ordering::Greater) => bail!("range {low}..={high} is empty"),
+                    Some(_) => Ok(()),
+                    None => bail!("range bounds {low} and {high} have different types"),
+                }
+            }
+            Self::In(set) => {
+                ensure!(!set.is_empty(), "set of allowed values is empty");
+                let first = &set[0];
+                for value in set.iter().skip(1) {
+                    ensure!(
+                        same_type(first, value),
+                        "set values {first} and {value} have different types"
+                    );
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Check that every operand is a literal of the given type.
+    pub fn check_literal_type(&self, expected: LiteralType) -> Result<()> {
+        let operand_type = |plaintext: &Plaintext<N>| match plaintext {
+            Plaintext::Literal(literal, _) => Some(literal.to_type()),
+            _ => None,
+        };
+        let operand_types = match self {
+            Self::Eq(operand) | Self::Ne(operand) => vec![operand_type(operand)],
+            Self::Gt(bound) | Self::Gte(bound) | Self::Lt(bound) | Self::Lte(bound) => {
+                vec![Some(bound.to_type())]
+            }
+            Self::Between(low, high) => vec![Some(low.to_type()), Some(high.to_type())],
+            Self::In(set) => set.iter().map(operand_type).collect(),
+        };
+        ensure!(
+            operand_types.iter().all(|t| *t == Some(expected)),
+            "operands must be {expected} literals"
+        );
+        Ok(())
+    }
+
+    /// Returns `true` if the value satisfies the predicate. Values of a different type than the
+    /// operand never satisfy an ordering predicate.
+    pub fn evaluate(&self, value: &Plaintext<N>) -> bool {
+        let ordering = |bound: &Literal<N>| match value {
+            Plaintext::Literal(literal, _) => compare_literals(literal, bound),
+            _ => None,
+        };
+        match self {
+            Self::Eq(expected) => value == expected,
+            Self::Ne(expected) => value != expected,
+            Self::Gt(bound) => ordering(bound) == Some(Ordering::Greater),
+            Self::Gte(bound) => {
+                matches!(ordering(bound), Some(Ordering::Greater | Ordering::Equal))
+            }
+            Self::Lt(bound) => ordering(bound) == Some(Ordering::Less),
+            Self::Lte(bound) => matches!(ordering(bound), Some(Ordering::Less | Ordering::Equal)),
+            Self::Between(low, high) => {
+                matches!(ordering(low), Some(Ordering::Greater | Ordering::Equal))
+                    && matches!(ordering(high), Some(Ordering::Less | Ordering::Equal))
+            }
+            Self::In(set) => set.contains(value),
+        }
+    }
+}
+
+impl<N: Network> Serialize for Predicate<N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Predicate::serialize(self, serializer)
+    }
+}
+
+// Manifests written before predicates were added hold a bare plaintext, which reads as `Eq`.
+impl<'de, N: Network> Deserialize<'de> for Predicate<N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        #[serde(bound(deserialize = "N: for<'a> Deserialize<'a>"))]
+        enum Encoded<N: Network> {
+            Predicate(#[serde(deserialize_with = "Predicate::deserialize")] Predicate<N>),
+            Plaintext(Plaintext<N>),
+        }
+
+        Ok(match Encoded::deserialize(deserializer)? {
+            Encoded::Predicate(predicate) => predicate,
+            Encoded::Plaintext(plaintext) => Self::Eq(plaintext),
+        })
+    }
+}
+
+/// Ensure the literal has a type that supports ordering.
+fn ensure_ordered<N: Network>(literal: &Literal<N>) -> Result<()> {
+    match compare_literals(literal, literal) {
+        Some(_) => Ok(()),
+        None => bail!(
+            "{} literals do not support ordering comparisons",
+            literal.to_type()
+        ),
+    }
+}
+
+/// Returns `true` if both plaintexts are literals of the same type, or both are structs or arrays.
+fn same_type<N: Network>(a: &Plaintext<N>, b: &Plaintext<N>) -> bool {
+    match (a, b) {
+        (Plaintext::Literal(a, _), Plaintext::Literal(b, _)) => a.to_type() == b.to_type(),
+        (Plaintext::Struct(..), Plaintext::Struct(..)) => true,
+        (Plaintext::Array(..), Plaintext::Array(..)) => true,
+        _ => false,
+    }
+}
+
+/// Compare two integer or field literals of the same type.
+fn compare_literals<N: Network>(a: &Literal<N>, b: &Literal<N>) -> Option<Ordering> {
+    match (a, b) {
+        (Literal::Field(a), Literal::Field(b)) => Some(if *a.is_less_than(b) {
+            Ordering::Less
+        } else if a == b {
+            Ordering::Equal
+        } else {
+            Ordering::Greater
+        }),
+        (Literal::I8(a), Literal::I8(b)) => Some((**a).cmp(&**b)),
+        (Literal::I16(a), Literal::I16(b)) => Some((**a).cmp(&**b)),
+        (Literal::I32(a), Literal::I32(b)) => Some((**a).cmp(&**b)),
+        (Literal::I64(a), Literal::I64(b)) => Some((**a).cmp(&**b)),
+        (Literal::I128(a), Literal::I128(b)) => Some((**a).cmp(&**b)),
+        (Literal::U8(a), Literal::U8(b)) => Some((**a).cmp(&**b)),
+        (Literal::U16(a), Literal::U16(b)) => Some((**a).cmp(&**b)),
+        (Literal::U32(a), Literal::U32(b)) => Some((**a).cmp(&**b)),
+        (Literal::U64(a), Literal::U64(b)) => Some((**a).cmp(&**b)),
+        (Literal::U128(a), Literal::U128(b)) => Some((**a).cmp(&**b)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::prelude::MainnetV0;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_predicates() {
+        let plaintext = |s: &str| Plaintext::<MainnetV0>::from_str(s).unwrap();
+        let literal = |s: &str| Literal::<MainnetV0>::from_str(s).unwrap();
+
+        let amount = plaintext("1500000u64");
+        assert!(Predicate::Gt(literal("1000000u64")).evaluate(&amount));
+        assert!(!Predicate::Lt(literal("1000000u64")).evaluate(&amount));
+        assert!(Predicate::Between(literal("1u64"), literal("1500000u64")).evaluate(&amount));
+        assert!(Predicate::In(vec![plaintext("1u64"), amount.clone()]).evaluate(&amount));
+        assert!(Predicate::Ne(plaintext("1u64")).evaluate(&amount));
+        // A bound of a different type never matches.
+        assert!(!Predicate::Gt(literal("1u32")).evaluate(&amount));
+        assert!(Predicate::Gte(literal("1field")).evaluate(&plaintext("2field")));
+
+        assert!(Predicate::Gt(literal("true")).check().is_err());
+        assert!(Predicate::Between(literal("1u64"), literal("5u32"))
+            .check()
+            .is_err());
+        assert!(Predicate::Between(literal("5u64"), literal("1u64"))
+            .check()
+            .is_err());
+        assert!(Predicate::In(vec![plaintext("1u64"), plaintext("true")])
+            .check()
+            .is_err());
+        assert!(Predicate::<MainnetV0>::In(vec![]).check().is_err());
+    }
+
+    #[test]
+    fn test_predicate_serialization() {
+        let plaintext = |s: &str| Plaintext::<MainnetV0>::from_str(s).unwrap();
+        let literal = |s: &str| Literal::<MainnetV0>::from_str(s).unwrap();
+
+        for predicate in [
+            Predicate::Eq(plaintext("1u64")),
+            Predicate::Between(literal("1u64"), literal("5u64")),
+            Predicate::In(vec![plaintext("1u64"), plaintext("2u64")]),
+        ] {
+            let json = serde_json::to_string(&predicate).unwrap();
+            assert_eq!(
+                serde_json::from_str::<Predicate<_>>(&json).unwrap(),
+                predicate
+            );
+        }
+        assert_eq!(
+            serde_json::from_value::<Predicate<MainnetV0>>(serde_json::json!({"Gt": "1u64"}))
+                .unwrap(),
+            Predicate::Gt(literal("1u64"))
+        );
+        // A bare plaintext is an equality predicate.
+        assert_eq!(
+            serde_json::from_value::<Predicate<MainnetV0>>(serde_json::json!("1u64")).unwrap(),
+            Predicate::Eq(plaintext("1u64"))
+        );
+        assert!(
+            serde_json::from_value::<Predicate<MainnetV0>>(serde_json::json!(
+                {"Gt": "1u64", "Lt": "5u64"}
+            ))
+            .is_err()
+        );
+    }
+}
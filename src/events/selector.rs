@@ -0,0 +1,280 @@
+use snarkvm::ledger::block::{Input, Output, Transition};
+use snarkvm::prelude::{Argument, Future, Identifier, Network, Plaintext};
+
+use anyhow::{anyhow, bail, ensure, Error, Result};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+/// The side of a transition a selector reads from.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ValueSource {
+    Input,
+    Output,
+}
+
+/// A step into a plaintext value.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Segment<N: Network> {
+    // A struct member.
+    Member(Identifier<N>),
+    // An array element.
+    Index(usize),
+}
+
+/// A path to a value inside a transition input or output, written as `input[1].owner` or
+/// `output[0].balances[3]`. Indexing a future output selects one of its arguments, so
+/// `output[2][0][1]` is the second argument of the first future awaited by output 2.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Selector<N: Network> {
+    source: ValueSource,
+    index: usize,
+    segments: Vec<Segment<N>>,
+}
+
+impl<N: Network> Selector<N> {
+    /// Create a new selector.
+    pub fn new(source: ValueSource, index: usize, segments: Vec<Segment<N>>) -> Self {
+        Self {
+            source,
+            index,
+            segments,
+        }
+    }
+
+    /// Get the side of the transition the selector reads from.
+    pub fn source(&self) -> ValueSource {
+        self.source
+    }
+
+    /// Get the input or output index.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Get the path walked inside the input or output.
+    pub fn segments(&self) -> &[Segment<N>] {
+        &self.segments
+    }
+
+    /// Returns the selected plaintext if the transition value is public and the path exists.
+    pub fn select<'a>(&self, transition: &'a Transition<N>) -> Option<&'a Plaintext<N>> {
+        match self.source {
+            ValueSource::Input => self.find(
+                transition
+                    .inputs()
+                    .get(self.index)
+                    .and_then(input_plaintext)?,
+            ),
+            ValueSource::Output => match transition.outputs().get(self.index)? {
+                Output::Future(_, Some(future)) => self.find_in_future(future),
+                output => self.find(output_plaintext(output)?),
+            },
+        }
+    }
+
+    /// Walk the path through struct members and array elements of the plaintext.
+    pub fn find<'a>(&self, plaintext: &'a Plaintext<N>) -> Option<&'a Plaintext<N>> {
+        walk(plaintext, &self.segments)
+    }
+
+    /// Walk the path through the arguments of the future and the futures it awaits, then
+    /// through the plaintext argument it reaches.
+    pub fn find_in_future<'a>(&self, future: &'a Future<N>) -> Option<&'a Plaintext<N>> {
+        future_plaintexts(future)
+            .into_iter()
+            .find(|(path, _)| {
+                path.len() <= self.segments.len()
+                    && path
+                        .iter()
+                        .zip(&self.segments)
+                        .all(|(index, segment)| *segment == Segment::Index(*index))
+            })
+            .and_then(|(path, plaintext)| walk(plaintext, &self.segments[path.len()..]))
+    }
+}
+
+impl<N: Network> FromStr for Selector<N> {
+    type Err = Error;
+
+    fn from_str(selector: &str) -> Result<Self> {
+        let (source, rest) = if let Some(rest) = selector.strip_prefix("input") {
+            (ValueSource::Input, rest)
+        } else if let Some(rest) = selector.strip_prefix("output") {
+            (ValueSource::Output, rest)
+        } else {
+            bail!("Selector '{selector}' must start with 'input' or 'output'")
+        };
+        let (index, mut rest) = parse_index(rest)
+            .ok_or_else(|| anyhow!("Selector '{selector}' is missing an index, e.g. 'input[0]'"))?;
+
+        let mut segments = Vec::new();
+        while !rest.is_empty() {
+            if let Some(member) = rest.strip_prefix('.') {
+                let end = member.find(['.', '[']).unwrap_or(member.len());
+                ensure!(end > 0, "Selector '{selector}' has an empty member name");
+                segments.push(Segment::Member(Identifier::from_str(&member[..end])?));
+                rest = &member[end..];
+            } else if let Some((index, remainder)) = parse_index(rest) {
+                segments.push(Segment::Index(index));
+                rest = remainder;
+            } else {
+                bail!("Selector '{selector}' has an invalid segment at '{rest}'")
+            }
+        }
+
+        Ok(Self::new(source, index, segments))
+    }
+}
+
+/// Walk the segments through struct members and array elements of the plaintext.
+fn walk<'a, N: Network>(
+    plaintext: &'a Plaintext<N>,
+    segments: &[Segment<N>],
+) -> Option<&'a Plaintext<N>> {
+    segments
+        .iter()
+        .try_fold(plaintext, |value, segment| match (segment, value) {
+            (Segment::Member(name), Plaintext::Struct(members, _)) => members.get(name),
+            (Segment::Index(index), Plaintext::Array(elements, _)) => elements.get(*index),
+            _ => None,
+        })
+}
+
+/// Returns the plaintext arguments of a future and of the futures it awaits, depth first, each
+/// with the argument indices leading to it.
+pub(crate) fn future_plaintexts<N: Network>(
+    future: &Future<N>,
+) -> Vec<(Vec<usize>, &Plaintext<N>)> {
+    fn visit<'a, N: Network>(
+        future: &'a Future<N>,
+        path: &mut Vec<usize>,
+        plaintexts: &mut Vec<(Vec<usize>, &'a Plaintext<N>)>,
+    ) {
+        for (index, argument) in future.arguments().iter().enumerate() {
+            path.push(index);
+            match argument {
+                Argument::Plaintext(plaintext) => plaintexts.push((path.clone(), plaintext)),
+                Argument::Future(awaited) => visit(awaited, path, plaintexts),
+            }
+            path.pop();
+        }
+    }
+
+    let mut plaintexts = vec![];
+    visit(future, &mut vec![], &mut plaintexts);
+    plaintexts
+}
+
+/// Parse a leading `[index]`, returning the index and the remaining string.
+fn parse_index(s: &str) -> Option<(usize, &str)> {
+    let s = s.strip_prefix('[')?;
+    let end = s.find(']')?;
+    Some((s[..end].parse().ok()?, &s[end + 1..]))
+}
+
+impl<N: Network> Display for Selector<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.source {
+            ValueSource::Input => write!(f, "input[{}]", self.index)?,
+            ValueSource::Output => write!(f, "output[{}]", self.index)?,
+        }
+        for segment in &self.segments {
+            match segment {
+                Segment::Member(name) => write!(f, ".{name}")?,
+                Segment::Index(index) => write!(f, "[{index}]")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<N: Network> Serialize for Selector<N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de, N: Network> Deserialize<'de> for Selector<N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let selector = String::deserialize(deserializer)?;
+        Self::from_str(&selector).map_err(de::Error::custom)
+    }
+}
+
+/// Returns the plaintext of a constant or public transition input.
+pub(crate) fn input_plaintext<N: Network>(input: &Input<N>) -> Option<&Plaintext<N>> {
+    match input {
+        Input::Constant(_, Some(plaintext)) | Input::Public(_, Some(plaintext)) => Some(plaintext),
+        _ => None,
+    }
+}
+
+/// Returns the plaintext of a constant or public transition output.
+pub(crate) fn output_plaintext<N: Network>(output: &Output<N>) -> Option<&Plaintext<N>> {
+    match output {
+        Output::Constant(_, Some(plaintext)) | Output::Public(_, Some(plaintext)) => {
+            Some(plaintext)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::prelude::MainnetV0;
+
+    type CurrentNetwork = MainnetV0;
+
+    #[test]
+    fn test_selector() {
+        let selector = Selector::<CurrentNetwork>::from_str("output[0].balances[3]").unwrap();
+        assert_eq!(selector.source(), ValueSource::Output);
+        assert_eq!(selector.index(), 0);
+        assert_eq!(selector.to_string(), "output[0].balances[3]");
+
+        let plaintext = Plaintext::<CurrentNetwork>::from_str(
+            "{ owner: 7field, balances: [1u64, 2u64, 3u64, 4u64] }",
+        )
+        .unwrap();
+        let expected = Plaintext::from_str("4u64").unwrap();
+        assert_eq!(selector.find(&plaintext), Some(&expected));
+
+        let owner = Selector::<CurrentNetwork>::from_str("input[1].owner").unwrap();
+        assert_eq!(
+            owner.find(&plaintext),
+            Some(&Plaintext::from_str("7field").unwrap())
+        );
+        let missing = Selector::<CurrentNetwork>::from_str("input[1].balances[4]").unwrap();
+        assert_eq!(missing.find(&plaintext), None);
+
+        let future = Future::<CurrentNetwork>::from_str(
+            "{ program_id: token.aleo, function_name: transfer_public, arguments: [ { program_id: credits.aleo, function_name: transfer_public, arguments: [ aleo1rhgdu77hgyqd3xjj8ucu3jj9r2krwz6mnzyd80gncr5fxcwlh5rsvzp9px, 5u64 ] }, { owner: 7field } ] }",
+        )
+        .unwrap();
+        let amount = Selector::<CurrentNetwork>::from_str("output[0][0][1]").unwrap();
+        assert_eq!(
+            amount.find_in_future(&future),
+            Some(&Plaintext::from_str("5u64").unwrap())
+        );
+        let owner = Selector::<CurrentNetwork>::from_str("output[0][1].owner").unwrap();
+        assert_eq!(
+            owner.find_in_future(&future),
+            Some(&Plaintext::from_str("7field").unwrap())
+        );
+        // A path ending on a future selects nothing.
+        let awaited = Selector::<CurrentNetwork>::from_str("output[0][0]").unwrap();
+        assert_eq!(awaited.find_in_future(&future), None);
+
+        let paths = future_plaintexts(&future)
+            .into_iter()
+            .map(|(path, _)| path)
+            .collect::<Vec<_>>();
+        assert_eq!(paths, vec![vec![0, 0], vec![0, 1], vec![1]]);
+
+        assert!(Selector::<CurrentNetwork>::from_str("input.owner").is_err());
+        assert!(Selector::<CurrentNetwork>::from_str("record[0]").is_err());
+        assert!(Selector::<CurrentNetwork>::from_str("input[0]..owner").is_err());
+    }
+}
@@ -0,0 +1,66 @@
+use super::{Condition, Selector};
+use snarkvm::prelude::Network;
+
+use anyhow::{anyhow, ensure, Result};
+use serde::{Deserialize, Serialize};
+
+/// Which outcomes of a sequence are reported.
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum SequenceReport {
+    // Sequences whose every step matched within the window.
+    #[default]
+    Completed,
+    // Sequences whose first step matched but that did not complete within the window.
+    TimedOut,
+    Both,
+}
+
+/// Selects transitions matching a series of conditions in order across blocks, such as an
+/// `unbond_public` followed by a `claim_unbond_public` within 400 blocks.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(bound(serialize = "N: Serialize", deserialize = "N: for<'a> Deserialize<'a>"))]
+pub struct SequenceFilter<N: Network> {
+    // Conditions matched by one transition each, in order.
+    pub steps: Vec<Condition<N>>,
+    // Maximum number of blocks between the first step and the last.
+    pub within: u32,
+    // One selector per step whose values must be equal for the steps to belong to the same
+    // sequence, e.g. the staker address of an unbond and of the following claim.
+    pub correlate: Option<Vec<Selector<N>>>,
+    #[serde(default)]
+    pub report: SequenceReport,
+}
+
+impl<N: Network> SequenceFilter<N> {
+    /// Check the steps, the window and the correlation selectors.
+    pub fn check(&self) -> Result<()> {
+        ensure!(!self.steps.is_empty(), "Sequence has no steps");
+        ensure!(
+            self.within > 0,
+            "Sequence window must be at least one block"
+        );
+        if let Some(correlate) = &self.correlate {
+            ensure!(
+                correlate.len() == self.steps.len(),
+                "Sequence has {} steps but {} correlation selectors",
+                self.steps.len(),
+                correlate.len()
+            );
+        }
+        for (index, step) in self.steps.iter().enumerate() {
+            step.check()
+                .map_err(|e| anyhow!("sequence step {index}: {e}"))?;
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if completed sequences are reported.
+    pub fn reports_completed(&self) -> bool {
+        self.report != SequenceReport::TimedOut
+    }
+
+    /// Returns `true` if timed out sequences are reported.
+    pub fn reports_timed_out(&self) -> bool {
+        self.report != SequenceReport::Completed
+    }
+}
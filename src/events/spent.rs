@@ -0,0 +1,12 @@
+use snarkvm::prelude::{Field, Network};
+
+use serde::{Deserialize, Serialize};
+
+/// Selects spends of records watched by the subscription. Records output to the subscription's
+/// view key are watched from the block they are created in.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(bound(serialize = "N: Serialize", deserialize = "N: for<'a> Deserialize<'a>"))]
+pub struct SpentFilter<N: Network> {
+    // Commitments of records to watch from the start, e.g. records received before subscribing.
+    pub commitments: Option<Vec<Field<N>>>,
+}
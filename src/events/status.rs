@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+
+/// How a transaction was confirmed in a block.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum ConfirmationStatus {
+    // The transaction was executed and finalized.
+    Accepted,
+    // The transaction failed and only its fee was charged.
+    Rejected,
+}
+
+/// The confirmation statuses a manifest reports. Aborted transactions are not stored by the
+/// ledger beyond their IDs, so they are reported by `EventKind::Aborted` manifests instead.
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum StatusFilter {
+    #[default]
+    Accepted,
+    Rejected,
+    Both,
+}
+
+impl StatusFilter {
+    /// Returns `true` if transactions with the status should be reported.
+    pub fn includes(&self, status: ConfirmationStatus) -> bool {
+        match self {
+            Self::Accepted => status == ConfirmationStatus::Accepted,
+            Self::Rejected => status == ConfirmationStatus::Rejected,
+            Self::Both => true,
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use super::Monitor;
+use crate::EventManifests;
+use snarkvm::ledger::store::ConsensusStorage;
+use snarkvm::prelude::Network;
+
+impl<N: Network, C: ConsensusStorage<N>> Monitor<N, C> {
+    /// Check manifests for errors and against the programs deployed on the ledger, returning
+    /// every problem found.
+    pub fn check_manifests(&self, manifests: &EventManifests<N>) -> Vec<String> {
+        let mut problems = manifests.problems();
+        for manifest in manifests.manifests() {
+            let program_ids = match manifest.checked_programs() {
+                Ok(program_ids) => program_ids,
+                Err(e) => {
+                    problems.push(format!("Event '{}': {e}", manifest.name));
+                    continue;
+                }
+            };
+            for program_id in program_ids {
+                match self.ledger.get_program(program_id) {
+                    Ok(program) => problems.extend(manifest.check_program(&program)),
+                    Err(_) => problems.push(format!(
+                        "Event '{}': program '{program_id}' is not deployed",
+                        manifest.name
+                    )),
+                }
+            }
+        }
+        problems
+    }
+}
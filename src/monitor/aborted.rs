@@ -0,0 +1,89 @@
+use super::Monitor;
+use crate::{AbortedEvent, EventDetails, EventManifest, EventPayLoad};
+use anyhow::Result;
+use snarkvm::ledger::store::ConsensusStorage;
+use snarkvm::prelude::Network;
+
+impl<N: Network, C: ConsensusStorage<N>> Monitor<N, C> {
+    /// Report every transaction the block aborted.
+    pub(crate) fn aborted_events(
+        &self,
+        event: &EventManifest<N>,
+        height: u32,
+    ) -> Result<Vec<EventPayLoad<N>>> {
+        let block = self.ledger.get_block(height)?;
+        Ok(block
+            .aborted_transaction_ids()
+            .iter()
+            .map(|transaction| {
+                EventPayLoad::new(
+                    event.name.clone(),
+                    event.description.clone(),
+                    height,
+                    None,
+                    EventDetails::Aborted(AbortedEvent::new(*transaction)),
+                )
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_helpers::{advance, sample_ledger, unspent_records, CurrentNetwork};
+    use super::*;
+    use snarkvm::prelude::{Address, PrivateKey, TestRng, Value};
+    use std::str::FromStr;
+
+    #[test]
+    fn test_aborted_events() {
+        let rng = &mut TestRng::default();
+        let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let address = Address::try_from(&private_key).unwrap();
+        let ledger = sample_ledger(&private_key, rng);
+
+        // Two transactions spend the same record, so the second one is aborted.
+        let records = unspent_records(&ledger, &private_key);
+        let transactions = [&records[1], &records[2]]
+            .into_iter()
+            .map(|fee_record| {
+                let inputs = [
+                    Value::Record(records[0].clone()),
+                    Value::from_str(&address.to_string()).unwrap(),
+                    Value::from_str("1u64").unwrap(),
+                ];
+                ledger
+                    .vm()
+                    .execute(
+                        &private_key,
+                        ("credits.aleo", "transfer_private"),
+                        inputs.into_iter(),
+                        Some(fee_record.clone()),
+                        0,
+                        None,
+                        rng,
+                    )
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        let aborted = transactions[1].id();
+        let height = advance(&ledger, &private_key, transactions, rng);
+
+        let manifest: EventManifest<CurrentNetwork> = serde_json::from_value(serde_json::json!({
+            "name": "aborted",
+            "description": "Aborted transactions",
+            "kind": "Aborted",
+            "actions": ["Notify"],
+        }))
+        .unwrap();
+        manifest.check().unwrap();
+
+        let monitor = Monitor::new(ledger);
+        let payloads = monitor.aborted_events(&manifest, height).unwrap();
+        assert_eq!(payloads.len(), 1);
+        let EventDetails::Aborted(details) = payloads[0].details() else {
+            panic!("expected an aborted transaction event");
+        };
+        assert_eq!(details.transaction(), &aborted);
+    }
+}
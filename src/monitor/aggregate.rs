@@ -0,0 +1,286 @@
+use super::{Monitor, Verdict};
+use crate::{
+    integer_value, AggregateEvent, AggregateFilter, AggregateFunction, EventDetails, EventManifest,
+    EventPayLoad, Subscription, WindowKind,
+};
+use anyhow::Result;
+use snarkvm::ledger::block::Transactions;
+use snarkvm::ledger::store::ConsensusStorage;
+use snarkvm::prelude::{Itertools, Network};
+use std::collections::VecDeque;
+
+/// A value taken from a transition the manifest matches.
+#[derive(Clone, Debug)]
+struct Sample<N: Network> {
+    // Block height the value was found at.
+    height: u32,
+    // The value.
+    value: i128,
+    // Transaction the value was found in.
+    transaction: N::TransactionID,
+    // Why the transaction failed verification, if the subscription is paranoid.
+    failure: Option<String>,
+}
+
+/// The samples in an aggregate manifest's current window.
+#[derive(Clone, Debug)]
+pub(crate) struct AggregateWindow<N: Network> {
+    // Samples in the window, oldest first.
+    samples: VecDeque<Sample<N>>,
+    // First block height of a tumbling window.
+    start: u32,
+    // Whether the threshold was met after the previous block.
+    met: bool,
+}
+
+impl<N: Network> AggregateWindow<N> {
+    /// An empty window starting at the height. It counts as meeting the threshold if its empty
+    /// aggregate does, so `Below` and `AtMost` thresholds fire only once the aggregate falls past
+    /// them, not as soon as the window opens.
+    fn new(filter: &AggregateFilter<N>, start: u32) -> Self {
+        let met = filter
+            .function
+            .apply(std::iter::empty())
+            .is_some_and(|aggregate| filter.threshold.is_met(aggregate));
+        Self {
+            samples: VecDeque::new(),
+            start,
+            met,
+        }
+    }
+}
+
+impl<N: Network, C: ConsensusStorage<N>> Monitor<N, C> {
+    /// Add the block's samples to the manifest's window and report when the aggregate crosses
+    /// the threshold. The event fires once per crossing, not on every block past it, and not
+    /// when a window opens already past it. For a paranoid subscription, each sample's
+    /// transaction is verified when it is sampled, and a crossing with a sample that failed is
+    /// reported as a verification failure.
+    pub(crate) fn aggregate_events(
+        &self,
+        subscription: &Subscription<N>,
+        event: &EventManifest<N>,
+        filter: &AggregateFilter<N>,
+        height: u32,
+        transactions: &Transactions<N>,
+    ) -> Result<Vec<EventPayLoad<N>>> {
+        // Move the window forward to end at this block.
+        let start = match filter.window_kind {
+            WindowKind::Sliding => height.saturating_sub(filter.window - 1),
+            WindowKind::Tumbling => height - height % filter.window,
+        };
+        let mut windows = self.aggregate_windows.lock();
+        let window = windows
+            .entry((*subscription.id(), event.name.clone()))
+            .or_insert_with(|| AggregateWindow::new(filter, start));
+        if filter.window_kind == WindowKind::Tumbling && start != window.start {
+            *window = AggregateWindow::new(filter, start);
+        }
+        window.start = start;
+        while window
+            .samples
+            .front()
+            .is_some_and(|sample| sample.height < start)
+        {
+            window.samples.pop_front();
+        }
+
+        for transaction in transactions.iter() {
+            let (transaction_id, status) = Self::confirmation(transaction)?;
+            let fee = Self::fee(transaction)?;
+            if !event.status.includes(status) || !event.matches_fee(fee.as_ref()) {
+                continue;
+            }
+            for transition in Self::executed_transitions(transaction) {
+                // Samples are not reported one by one, so decisions are not proven.
+                let Verdict::Accepted(_) = self.verdict(subscription, event, transition, false)
+                else {
+                    continue;
+                };
+                let sample = match (&filter.value, filter.function) {
+                    (_, AggregateFunction::Count) => Some(1),
+                    (Some(selector), _) => selector.select(transition).and_then(integer_value),
+                    (None, _) => None,
+                };
+                if let Some(value) = sample {
+                    let failure = match subscription.is_paranoid() {
+                        true => self
+                            .verification_failure(height, transaction)?
+                            .map(|(_, error)| error),
+                        false => None,
+                    };
+                    window.samples.push_back(Sample {
+                        height,
+                        value,
+                        transaction: transaction_id,
+                        failure,
+                    });
+                }
+            }
+        }
+
+        let aggregate = filter
+            .function
+            .apply(window.samples.iter().map(|sample| sample.value));
+        let met = aggregate.is_some_and(|aggregate| filter.threshold.is_met(aggregate));
+        let crossed = met && !window.met;
+        window.met = met;
+
+        let mut payloads = vec![];
+        if let (true, Some(aggregate)) = (crossed, aggregate) {
+            let details = AggregateEvent::new(
+                filter.function,
+                aggregate,
+                window.samples.len() as u64,
+                start,
+                window
+                    .samples
+                    .iter()
+                    .map(|sample| sample.transaction)
+                    .unique()
+                    .collect(),
+            );
+            let payload = EventPayLoad::new(
+                event.name.clone(),
+                event.description.clone(),
+                height,
+                None,
+                EventDetails::Aggregate(details),
+            );
+            let failure = window.samples.iter().find_map(|sample| {
+                sample
+                    .failure
+                    .clone()
+                    .map(|error| (sample.transaction, error))
+            });
+            payloads.push(match failure {
+                Some((transaction, error)) => payload.into_verification_failure(transaction, error),
+                None => payload,
+            });
+        }
+        Ok(payloads)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_helpers::{advance, sample_ledger, CurrentNetwork};
+    use super::*;
+    use crate::{EventKind, EventManifests};
+    use serde_json::json;
+    use snarkvm::prelude::{Address, PrivateKey, TestRng, Value};
+    use std::str::FromStr;
+
+    #[test]
+    fn test_aggregate_events() {
+        let rng = &mut TestRng::default();
+        let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let ledger = sample_ledger(&private_key, rng);
+        let monitor = Monitor::new(ledger.clone());
+        let receiver = Address::try_from(&PrivateKey::new(rng).unwrap()).unwrap();
+
+        let subscribe = |name: &str, filter: serde_json::Value| {
+            let manifest = serde_json::from_value(json!({
+                "name": name,
+                "description": "",
+                "kind": {"Aggregate": filter},
+                "function": "transfer_public",
+                "actions": ["Notify"],
+            }))
+            .unwrap();
+            Subscription::new(EventManifests::new(vec![manifest])).unwrap()
+        };
+        // At least two public transfers in blocks 0 to 2, 3 to 5 and so on.
+        let busy = subscribe(
+            "busy",
+            json!({"function": "Count", "window": 3, "window_kind": "Tumbling",
+                   "threshold": {"AtLeast": 2}}),
+        );
+        // Less than 5 microcredits sent in the same windows. Every window opens below it.
+        let quiet = subscribe(
+            "quiet",
+            json!({"function": "Sum", "value": "input[1]", "window": 3, "window_kind": "Tumbling",
+                   "threshold": {"Below": 5}}),
+        );
+        // Less than 3 microcredits sent in the last two blocks.
+        let dropped = subscribe(
+            "dropped",
+            json!({"function": "Sum", "value": "input[1]", "window": 2,
+                   "threshold": {"Below": 3}}),
+        );
+
+        // Add a block of public transfers of the amounts and return the aggregates each
+        // subscription reports for it.
+        let block = |amounts: &[u64], rng: &mut TestRng| {
+            let transactions = amounts
+                .iter()
+                .map(|amount| {
+                    let inputs = [
+                        Value::from_str(&receiver.to_string()).unwrap(),
+                        Value::from_str(&format!("{amount}u64")).unwrap(),
+                    ];
+                    ledger
+                        .vm()
+                        .execute(
+                            &private_key,
+                            ("credits.aleo", "transfer_public"),
+                            inputs.into_iter(),
+                            None,
+                            0,
+                            None,
+                            rng,
+                        )
+                        .unwrap()
+                })
+                .collect::<Vec<_>>();
+            let height = advance(&ledger, &private_key, transactions, rng);
+            let transactions = ledger.get_transactions(height).unwrap();
+            [&busy, &quiet, &dropped].map(|subscription| {
+                let event = &subscription.events()[0];
+                let EventKind::Aggregate(filter) = &event.kind else {
+                    unreachable!();
+                };
+                monitor
+                    .aggregate_events(subscription, event, filter, height, &transactions)
+                    .unwrap()
+                    .iter()
+                    .map(|payload| {
+                        serde_json::to_value(payload.details()).unwrap()["Aggregate"].clone()
+                    })
+                    .collect::<Vec<_>>()
+            })
+        };
+
+        // Samples accumulate without crossing a threshold. The windows below their thresholds
+        // from the start do not fire.
+        let [busy, quiet, dropped] = block(&[2], rng);
+        assert!(busy.is_empty() && quiet.is_empty() && dropped.is_empty());
+
+        // The second transfer in the first tumbling window crosses its threshold.
+        let [busy, quiet, dropped] = block(&[5], rng);
+        assert_eq!(busy.len(), 1);
+        assert_eq!(busy[0]["value"], 2);
+        assert_eq!(busy[0]["samples"], 2);
+        assert_eq!(busy[0]["window_start"], 0);
+        assert!(quiet.is_empty() && dropped.is_empty());
+
+        // The tumbling windows reset at height 3. The count crosses its threshold again, while
+        // the sum opens below its threshold and stays there without firing.
+        let [busy, quiet, dropped] = block(&[1, 1], rng);
+        assert_eq!(busy.len(), 1);
+        assert_eq!(busy[0]["value"], 2);
+        assert_eq!(busy[0]["window_start"], 3);
+        assert!(quiet.is_empty() && dropped.is_empty());
+
+        // The count stays past its threshold, so it does not fire again. The sliding sum falls
+        // below its threshold once the transfer of 5 leaves the window.
+        let [busy, quiet, dropped] = block(&[], rng);
+        assert!(busy.is_empty() && quiet.is_empty());
+        assert_eq!(dropped.len(), 1);
+        assert_eq!(dropped[0]["value"], 2);
+        assert_eq!(dropped[0]["window_start"], 3);
+
+        let [busy, quiet, dropped] = block(&[], rng);
+        assert!(busy.is_empty() && quiet.is_empty() && dropped.is_empty());
+    }
+}
@@ -0,0 +1,125 @@
+use super::Monitor;
+use crate::{BlockEvent, BlockFilter, EventDetails, EventManifest, EventPayLoad};
+use anyhow::Result;
+use snarkvm::ledger::store::ConsensusStorage;
+use snarkvm::prelude::{Literal, Network, Plaintext, U32};
+
+impl<N: Network, C: ConsensusStorage<N>> Monitor<N, C> {
+    /// Report the block if it matches the manifest's block filter.
+    pub(crate) fn block_events(
+        &self,
+        event: &EventManifest<N>,
+        filter: &BlockFilter<N>,
+        height: u32,
+    ) -> Result<Vec<EventPayLoad<N>>> {
+        let block = self.ledger.get_block(height)?;
+        // The genesis block has nothing to compare against.
+        let previous = match height {
+            0 => None,
+            _ => Some(self.ledger.get_header(height - 1)?),
+        };
+        let num_transactions = block.transactions().len() as u32;
+        let provers = block
+            .solutions()
+            .iter()
+            .flat_map(|solutions| solutions.values())
+            .map(|solution| solution.address())
+            .collect::<Vec<_>>();
+
+        let matches = filter.matches_timestamp(
+            block.timestamp(),
+            previous.as_ref().map(|previous| previous.timestamp()),
+        ) && filter
+            .empty
+            .is_none_or(|empty| empty == (num_transactions == 0))
+            && filter.transactions.as_ref().is_none_or(|predicate| {
+                predicate.evaluate(&Plaintext::from(Literal::U32(U32::new(num_transactions))))
+            })
+            && (!filter.coinbase_target_changed
+                || previous
+                    .as_ref()
+                    .is_some_and(|previous| previous.coinbase_target() != block.coinbase_target()))
+            && filter.prover.is_none_or(|prover| provers.contains(&prover));
+        if !matches {
+            return Ok(vec![]);
+        }
+
+        let details = BlockEvent::new(
+            block.hash(),
+            block.previous_hash(),
+            block.round(),
+            block.timestamp(),
+            num_transactions,
+            block.coinbase_target(),
+            block.proof_target(),
+            provers,
+        );
+        Ok(vec![EventPayLoad::new(
+            event.name.clone(),
+            event.description.clone(),
+            height,
+            None,
+            EventDetails::Block(details),
+        )])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_helpers::{advance, sample_ledger, CurrentNetwork};
+    use super::*;
+    use crate::EventKind;
+    use serde_json::json;
+    use snarkvm::prelude::{Address, PrivateKey, TestRng};
+
+    #[test]
+    fn test_block_events() {
+        let rng = &mut TestRng::default();
+        let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let ledger = sample_ledger(&private_key, rng);
+        let height = advance(&ledger, &private_key, vec![], rng);
+        let monitor = Monitor::new(ledger.clone());
+
+        let block_events = |filter: serde_json::Value, height: u32| {
+            let manifest: EventManifest<CurrentNetwork> = serde_json::from_value(json!({
+                "name": "blocks",
+                "description": "",
+                "kind": {"Block": filter},
+                "actions": ["Notify"],
+            }))
+            .unwrap();
+            manifest.check().unwrap();
+            let EventKind::Block(filter) = &manifest.kind else {
+                unreachable!();
+            };
+            monitor.block_events(&manifest, filter, height).unwrap()
+        };
+
+        // The genesis block holds four transfers, the next block none.
+        let busy = json!({"empty": false, "transactions": {"Eq": "4u32"}});
+        let payloads = block_events(busy.clone(), 0);
+        assert_eq!(payloads.len(), 1);
+        let details = &serde_json::to_value(payloads[0].details()).unwrap()["Block"];
+        let genesis = ledger.get_block(0).unwrap();
+        assert_eq!(details["hash"], genesis.hash().to_string());
+        assert_eq!(details["transactions"], 4);
+        assert_eq!(details["timestamp"], genesis.timestamp());
+        assert!(block_events(busy, height).is_empty());
+
+        let empty = json!({"empty": true});
+        assert!(block_events(empty.clone(), 0).is_empty());
+        let payloads = block_events(empty, height);
+        assert_eq!(payloads.len(), 1);
+        let details = &serde_json::to_value(payloads[0].details()).unwrap()["Block"];
+        assert_eq!(details["previous_hash"], genesis.hash().to_string());
+        assert_eq!(details["provers"], json!([]));
+
+        // An empty filter fires on every block, but the genesis block has no timestamp gap.
+        assert_eq!(block_events(json!({}), 0).len(), 1);
+        assert!(block_events(json!({"timestamp_gap": 1}), 0).is_empty());
+
+        // Neither block holds a solution from a prover.
+        let prover = Address::try_from(&private_key).unwrap();
+        assert!(block_events(json!({"prover": prover.to_string()}), height).is_empty());
+    }
+}
@@ -0,0 +1,21 @@
+use anyhow::{anyhow, Result};
+use std::any::Any;
+
+// A process is generic over the circuit environment, which is fixed by the network but cannot
+// be named for a generic `N`. Callers match on `N::ID` and use these casts to move values between
+// `N` and the concrete network it was matched to.
+
+/// Cast a value to the same type under another name.
+pub(super) fn cast<T: 'static, U: 'static>(value: T) -> Result<U> {
+    (Box::new(value) as Box<dyn Any>)
+        .downcast::<U>()
+        .map(|value| *value)
+        .map_err(|_| anyhow!("Network type mismatch"))
+}
+
+/// Cast a reference to the same type under another name.
+pub(super) fn cast_ref<T: 'static, U: 'static>(value: &T) -> Result<&U> {
+    (value as &dyn Any)
+        .downcast_ref::<U>()
+        .ok_or_else(|| anyhow!("Network type mismatch"))
+}
@@ -0,0 +1,163 @@
+use super::Monitor;
+use crate::{
+    CommitteeChange, CommitteeEvent, CommitteeFilter, EventDetails, EventManifest, EventPayLoad,
+    ValidatorState,
+};
+use anyhow::Result;
+use indexmap::IndexMap;
+use snarkvm::ledger::committee::Committee;
+use snarkvm::ledger::store::ConsensusStorage;
+use snarkvm::prelude::{Address, Network};
+
+impl<N: Network, C: ConsensusStorage<N>> Monitor<N, C> {
+    /// Compare the committee at the block with the one at the previous block and report each
+    /// change the manifest selects.
+    pub(crate) fn committee_events(
+        &self,
+        event: &EventManifest<N>,
+        filter: &CommitteeFilter<N>,
+        height: u32,
+    ) -> Result<Vec<EventPayLoad<N>>> {
+        // The genesis committee has nothing to compare against.
+        if height == 0 {
+            return Ok(vec![]);
+        }
+        let (Some(previous), Some(current)) = (
+            self.ledger.get_committee(height - 1)?,
+            self.ledger.get_committee(height)?,
+        ) else {
+            return Ok(vec![]);
+        };
+        let before = validator_states(&previous);
+        let after = validator_states(&current);
+
+        let mut payloads = vec![];
+        let validators = before
+            .keys()
+            .chain(after.keys().filter(|v| !before.contains_key(*v)));
+        for validator in validators {
+            let old = before.get(validator).copied();
+            let new = after.get(validator).copied();
+            let changes = match (old, new) {
+                (None, Some(_)) => vec![CommitteeChange::Added],
+                (Some(_), None) => vec![CommitteeChange::Removed],
+                (Some(old), Some(new)) => [
+                    (old.stake() != new.stake(), CommitteeChange::Stake),
+                    (
+                        old.commission() != new.commission(),
+                        CommitteeChange::Commission,
+                    ),
+                    (old.is_open() != new.is_open(), CommitteeChange::IsOpen),
+                ]
+                .into_iter()
+                .filter_map(|(changed, change)| changed.then_some(change))
+                .collect(),
+                (None, None) => vec![],
+            };
+            let stake = (
+                old.map_or(0, |state| state.stake()),
+                new.map_or(0, |state| state.stake()),
+            );
+            for change in changes {
+                if !filter.includes(validator, change, stake) {
+                    continue;
+                }
+                payloads.push(EventPayLoad::new(
+                    event.name.clone(),
+                    event.description.clone(),
+                    height,
+                    None,
+                    EventDetails::Committee(CommitteeEvent::new(*validator, change, old, new)),
+                ));
+            }
+        }
+        Ok(payloads)
+    }
+}
+
+/// Returns the stake, openness and commission of each committee member.
+fn validator_states<N: Network>(committee: &Committee<N>) -> IndexMap<Address<N>, ValidatorState> {
+    committee
+        .members()
+        .iter()
+        .map(|(address, (stake, is_open, commission))| {
+            (*address, ValidatorState::new(*stake, *is_open, *commission))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_helpers::{advance, sample_ledger, CurrentNetwork};
+    use super::*;
+    use crate::EventKind;
+    use serde_json::json;
+    use snarkvm::prelude::{PrivateKey, TestRng};
+
+    #[test]
+    fn test_committee_events() {
+        let rng = &mut TestRng::default();
+        let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let ledger = sample_ledger(&private_key, rng);
+        let monitor = Monitor::new(ledger.clone());
+
+        let committee_events = |filter: serde_json::Value, height: u32| {
+            let manifest: EventManifest<CurrentNetwork> = serde_json::from_value(json!({
+                "name": "committee",
+                "description": "",
+                "kind": {"Committee": filter},
+                "actions": ["Notify"],
+            }))
+            .unwrap();
+            manifest.check().unwrap();
+            let EventKind::Committee(filter) = &manifest.kind else {
+                unreachable!();
+            };
+            monitor
+                .committee_events(&manifest, filter, height)
+                .unwrap()
+                .iter()
+                .map(|payload| {
+                    serde_json::to_value(payload.details()).unwrap()["Committee"].clone()
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // Stake changes are only selected with a threshold.
+        let manifest: EventManifest<CurrentNetwork> = serde_json::from_value(json!({
+            "name": "committee",
+            "description": "",
+            "kind": {"Committee": {"changes": ["Added", "Stake"]}},
+            "actions": ["Notify"],
+        }))
+        .unwrap();
+        assert!(manifest.check().is_err());
+
+        // The genesis committee has nothing to compare against.
+        assert!(committee_events(json!({"stake_change": 1}), 0).is_empty());
+
+        // An empty block only pays staking rewards, which raise every validator's stake.
+        let height = advance(&ledger, &private_key, vec![], rng);
+        let members = ledger
+            .get_committee(height)
+            .unwrap()
+            .unwrap()
+            .members()
+            .len();
+        let changes = committee_events(json!({"stake_change": 1}), height);
+        assert_eq!(changes.len(), members);
+        for change in &changes {
+            assert_eq!(change["change"], "Stake");
+            assert!(
+                change["after"]["stake"].as_u64().unwrap()
+                    > change["before"]["stake"].as_u64().unwrap()
+            );
+        }
+
+        // Rewards stay below a large enough threshold, and no member joined or left.
+        let threshold = json!({"stake_change": 1_000_000_000_000u64});
+        assert!(committee_events(threshold, height).is_empty());
+        let membership = json!({"changes": ["Added", "Removed"]});
+        assert!(committee_events(membership, height).is_empty());
+    }
+}
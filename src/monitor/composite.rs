@@ -0,0 +1,46 @@
+use super::Monitor;
+use crate::{CompositeEvent, CompositeFilter, EventDetails, EventManifest, EventPayLoad};
+use anyhow::Result;
+use snarkvm::ledger::block::Transactions;
+use snarkvm::ledger::store::ConsensusStorage;
+use snarkvm::prelude::{Itertools, Network};
+
+impl<N: Network, C: ConsensusStorage<N>> Monitor<N, C> {
+    /// Find the transactions in a block holding every transition the manifest asks for.
+    pub(crate) fn composite_events(
+        &self,
+        event: &EventManifest<N>,
+        filter: &CompositeFilter<N>,
+        height: u32,
+        transactions: &Transactions<N>,
+    ) -> Result<Vec<EventPayLoad<N>>> {
+        let mut payloads = vec![];
+        for transaction in transactions.iter() {
+            let (transaction_id, status) = Self::confirmation(transaction)?;
+            let fee = Self::fee(transaction)?;
+            if !event.status.includes(status) || !event.matches_fee(fee.as_ref()) {
+                continue;
+            }
+            let transitions = Self::executed_transitions(transaction).collect_vec();
+            let Some(matched) = filter.match_transitions(&transitions) else {
+                continue;
+            };
+            let details = CompositeEvent::new(
+                transaction_id,
+                status,
+                matched
+                    .into_iter()
+                    .map(|index| *transitions[index].id())
+                    .collect(),
+            );
+            payloads.push(EventPayLoad::new(
+                event.name.clone(),
+                event.description.clone(),
+                height,
+                fee,
+                EventDetails::Composite(details),
+            ));
+        }
+        Ok(payloads)
+    }
+}
@@ -0,0 +1,437 @@
+use super::cast::{cast, cast_ref};
+use super::Monitor;
+use crate::{DecisionFunction, DecisionProof, EventManifest, Subscription};
+use anyhow::{anyhow, bail, ensure, Result};
+use rand::thread_rng;
+use snarkvm::circuit::{Aleo, AleoCanaryV0, AleoTestnetV0, AleoV0};
+use snarkvm::console::network::{CanaryV0, MainnetV0, TestnetV0};
+use snarkvm::ledger::block::{Execution, Transition};
+use snarkvm::ledger::query::QueryTrait;
+use snarkvm::ledger::store::ConsensusStorage;
+use snarkvm::prelude::{
+    Field, Identifier, Literal, Locator, Network, Plaintext, PrivateKey, ProgramID, StatePath,
+    Value,
+};
+use snarkvm::synthesizer::program::Program;
+use snarkvm::synthesizer::{Process, Response};
+use tracing::warn;
+
+/// Whether a manifest reports a transition.
+pub(super) enum Verdict<N: Network> {
+    // The transition does not match, or the decision function returned `false`.
+    Rejected,
+    // The transition is reported, with a proof of the decision if the manifest asked for one.
+    Accepted(Option<DecisionProof<N>>),
+}
+
+/// A scratch process a subscription's decision programs are loaded into and evaluated in.
+#[derive(Clone)]
+pub(crate) struct DecisionProcess<N: Network> {
+    process: Process<N>,
+    // Key that signs the requests. Decisions only read public values, so any key will do.
+    private_key: PrivateKey<N>,
+}
+
+impl<N: Network> DecisionProcess<N> {
+    /// Load a process holding only `credits.aleo`.
+    fn load() -> Result<Self> {
+        Ok(Self {
+            process: Process::load()?,
+            private_key: PrivateKey::new(&mut thread_rng())?,
+        })
+    }
+
+    /// Add a decision program unless it is already loaded, which type-checks it. Programs are
+    /// keyed by name, so the manifests of a subscription cannot use different programs under the
+    /// same name.
+    fn add_program(&mut self, program: &Program<N>) -> Result<()> {
+        if self.process.contains_program(program.id()) {
+            ensure!(
+                self.process.get_program(program.id())? == program,
+                "A different decision program named {} is already loaded",
+                program.id()
+            );
+            return Ok(());
+        }
+        self.process.add_program(program)
+    }
+
+    /// Evaluate the function and return its boolean output.
+    fn decide(
+        &self,
+        program_id: &ProgramID<N>,
+        function: &Identifier<N>,
+        inputs: Vec<Value<N>>,
+    ) -> Result<bool> {
+        macro_rules! evaluate {
+            ($network:ty, $aleo:ty) => {{
+                let response = evaluate::<$network, $aleo>(
+                    cast_ref(&self.process)?,
+                    cast_ref(&self.private_key)?,
+                    cast_ref(program_id)?,
+                    cast_ref(function)?,
+                    cast(inputs)?,
+                )?;
+                cast::<_, Response<N>>(response)?
+            }};
+        }
+        let response = match N::ID {
+            MainnetV0::ID => evaluate!(MainnetV0, AleoV0),
+            TestnetV0::ID => evaluate!(TestnetV0, AleoTestnetV0),
+            CanaryV0::ID => evaluate!(CanaryV0, AleoCanaryV0),
+            id => bail!("Unknown network ID ({id})"),
+        };
+        is_accepted(&response)
+    }
+
+    /// Execute the function and prove the execution against the state root.
+    fn prove(
+        &self,
+        program_id: &ProgramID<N>,
+        function: &Identifier<N>,
+        plaintexts: Vec<Plaintext<N>>,
+        state_root: N::StateRoot,
+    ) -> Result<DecisionProof<N>> {
+        let inputs = plaintexts.iter().cloned().map(Value::Plaintext).collect();
+        macro_rules! prove {
+            ($network:ty, $aleo:ty) => {{
+                let execution = prove::<$network, $aleo>(
+                    cast_ref(&self.process)?,
+                    cast_ref(&self.private_key)?,
+                    cast_ref(program_id)?,
+                    cast_ref(function)?,
+                    cast(inputs)?,
+                    cast(StateRootQuery::<N>(state_root))?,
+                )?;
+                cast::<_, Option<Execution<N>>>(execution)?
+            }};
+        }
+        let execution = match N::ID {
+            MainnetV0::ID => prove!(MainnetV0, AleoV0),
+            TestnetV0::ID => prove!(TestnetV0, AleoTestnetV0),
+            CanaryV0::ID => prove!(CanaryV0, AleoCanaryV0),
+            id => bail!("Unknown network ID ({id})"),
+        }
+        .ok_or_else(|| anyhow!("The decision function returned false when proven"))?;
+        let verifying_key = self.process.get_verifying_key(*program_id, *function)?;
+        Ok(DecisionProof::new(
+            *program_id,
+            *function,
+            plaintexts,
+            execution,
+            verifying_key,
+        ))
+    }
+}
+
+impl<N: Network, C: ConsensusStorage<N>> Monitor<N, C> {
+    /// Load the decision programs of a subscription into a process of its own, copied from one
+    /// holding only `credits.aleo`. Fails if a program does not type-check.
+    pub(super) fn load_decisions(&self, subscription: &Subscription<N>) -> Result<()> {
+        let programs = subscription
+            .events()
+            .iter()
+            .filter_map(|event| event.decision.as_ref())
+            .map(|decision| &decision.program)
+            .collect::<Vec<_>>();
+        if programs.is_empty() {
+            return Ok(());
+        }
+        let mut process = {
+            let mut base = self.decision_process.lock();
+            if base.is_none() {
+                *base = Some(DecisionProcess::load()?);
+            }
+            base.clone().expect("the decision process was just loaded")
+        };
+        for program in programs {
+            process.add_program(program)?;
+        }
+        self.decision_processes
+            .lock()
+            .insert(*subscription.id(), process);
+        Ok(())
+    }
+
+    /// Decide whether the manifest reports the transition. It must match the manifest's filters
+    /// and, if the manifest has a decision function, the function must return `true`. Decisions
+    /// are proven if the manifest asks for it and `prove` is set. A decision that fails to run
+    /// rejects the transition alone, so the rest of the block is still searched.
+    pub(super) fn verdict(
+        &self,
+        subscription: &Subscription<N>,
+        event: &EventManifest<N>,
+        transition: &Transition<N>,
+        prove: bool,
+    ) -> Verdict<N> {
+        if !event.matches(transition) {
+            return Verdict::Rejected;
+        }
+        let Some(decision) = &event.decision else {
+            return Verdict::Accepted(None);
+        };
+        match self.decide(subscription, decision, transition, prove) {
+            Ok(verdict) => verdict,
+            Err(error) => {
+                warn!(
+                    "Decision on transition {} for event '{}' failed: {error}",
+                    transition.id(),
+                    event.name
+                );
+                Verdict::Rejected
+            }
+        }
+    }
+
+    /// Run the decision function on the transition, in the subscription's process.
+    fn decide(
+        &self,
+        subscription: &Subscription<N>,
+        decision: &DecisionFunction<N>,
+        transition: &Transition<N>,
+        prove: bool,
+    ) -> Result<Verdict<N>> {
+        let function = decision
+            .program
+            .functions()
+            .get(&decision.function)
+            .ok_or_else(|| anyhow!("Decision function {} does not exist", decision.function))?;
+        // A transition without a public value for every input cannot be decided on.
+        let Some(plaintexts) = decision.inputs(transition) else {
+            return Ok(Verdict::Rejected);
+        };
+        if plaintexts.len() != function.inputs().len() {
+            return Ok(Verdict::Rejected);
+        }
+        let plaintexts = plaintexts.into_iter().cloned().collect::<Vec<_>>();
+        let inputs = plaintexts.iter().cloned().map(Value::Plaintext).collect();
+
+        // Work on a copy of the process so the lock is not held while proving, which takes
+        // seconds. The loaded programs and keys are shared with the copy.
+        let process = self
+            .decision_processes
+            .lock()
+            .get(subscription.id())
+            .cloned()
+            .ok_or_else(|| {
+                anyhow!(
+                    "The decision programs of subscription {} are not loaded",
+                    subscription.id()
+                )
+            })?;
+        let program_id = decision.program.id();
+
+        if !process.decide(program_id, &decision.function, inputs)? {
+            return Ok(Verdict::Rejected);
+        }
+        if !(prove && decision.prove) {
+            return Ok(Verdict::Accepted(None));
+        }
+        // The decision was made, so a failed proof leaves the transition reported unproven.
+        let state_root = self.ledger.latest_state_root();
+        match process.prove(program_id, &decision.function, plaintexts, state_root) {
+            Ok(proof) => Ok(Verdict::Accepted(Some(proof))),
+            Err(error) => {
+                warn!(
+                    "Failed to prove the decision on transition {}, reporting it unproven: {error}",
+                    transition.id()
+                );
+                Ok(Verdict::Accepted(None))
+            }
+        }
+    }
+}
+
+/// Authorize and evaluate a function without proving it.
+fn evaluate<N: Network, A: Aleo<Network = N>>(
+    process: &Process<N>,
+    private_key: &PrivateKey<N>,
+    program_id: &ProgramID<N>,
+    function: &Identifier<N>,
+    inputs: Vec<Value<N>>,
+) -> Result<Response<N>> {
+    let authorization = process.authorize::<A, _>(
+        private_key,
+        *program_id,
+        *function,
+        inputs.into_iter(),
+        &mut thread_rng(),
+    )?;
+    process.evaluate::<A>(authorization)
+}
+
+/// Execute a function and prove the execution if it returns `true`.
+fn prove<N: Network, A: Aleo<Network = N>>(
+    process: &Process<N>,
+    private_key: &PrivateKey<N>,
+    program_id: &ProgramID<N>,
+    function: &Identifier<N>,
+    inputs: Vec<Value<N>>,
+    query: StateRootQuery<N>,
+) -> Result<Option<Execution<N>>> {
+    let rng = &mut thread_rng();
+    let authorization =
+        process.authorize::<A, _>(private_key, *program_id, *function, inputs.into_iter(), rng)?;
+    let (response, mut trace) = process.execute::<A, _>(authorization, rng)?;
+    if !is_accepted(&response)? {
+        return Ok(None);
+    }
+    trace.prepare(query)?;
+    let locator = Locator::new(*program_id, *function).to_string();
+    Ok(Some(trace.prove_execution::<A, _>(&locator, rng)?))
+}
+
+/// Returns the boolean a decision function returned.
+fn is_accepted<N: Network>(response: &Response<N>) -> Result<bool> {
+    match response.outputs() {
+        [Value::Plaintext(Plaintext::Literal(Literal::Boolean(result), _))] => Ok(**result),
+        _ => bail!("The decision function did not return a boolean"),
+    }
+}
+
+/// Answers the proving queries of a decision with a fixed state root. Decision functions take
+/// no records, so no state paths are needed.
+struct StateRootQuery<N: Network>(N::StateRoot);
+
+impl<N: Network> QueryTrait<N> for StateRootQuery<N> {
+    fn current_state_root(&self) -> Result<N::StateRoot> {
+        Ok(self.0)
+    }
+
+    fn get_state_path_for_commitment(&self, _commitment: &Field<N>) -> Result<StatePath<N>> {
+        bail!("Decision functions cannot take records")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_helpers::{advance, sample_ledger, unspent_records, CurrentNetwork};
+    use super::*;
+    use crate::{EventDetails, EventManifests};
+    use snarkvm::prelude::{Address, TestRng};
+    use std::str::FromStr;
+
+    #[test]
+    fn test_failed_decision_rejects_one_transition() {
+        let rng = &mut TestRng::default();
+        let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let address = Address::try_from(&private_key).unwrap();
+        let ledger = sample_ledger(&private_key, rng);
+
+        // Move 1 and 2 microcredits to the public balance, paying the fees with other records.
+        let mut records = unspent_records(&ledger, &private_key).into_iter();
+        let transactions = ["1u64", "2u64"]
+            .into_iter()
+            .map(|amount| {
+                let inputs = [
+                    Value::Record(records.next().unwrap()),
+                    Value::from_str(&address.to_string()).unwrap(),
+                    Value::from_str(amount).unwrap(),
+                ];
+                ledger
+                    .vm()
+                    .execute(
+                        &private_key,
+                        ("credits.aleo", "transfer_private_to_public"),
+                        inputs.into_iter(),
+                        records.next(),
+                        0,
+                        None,
+                        rng,
+                    )
+                    .unwrap()
+            })
+            .collect();
+        let height = advance(&ledger, &private_key, transactions, rng);
+
+        // The decision halts on an amount of 1.
+        let manifest: EventManifest<CurrentNetwork> = serde_json::from_value(serde_json::json!({
+            "name": "smallTransfers",
+            "description": "Transfers the decision function accepts",
+            "program": "credits.aleo",
+            "function": "transfer_private_to_public",
+            "decision": {
+                "program": "program decision_test.aleo;\n\nfunction decide:\n    input r0 as u64.public;\n    assert.neq r0 1u64;\n    gte r0 0u64 into r1;\n    output r1 as boolean.public;\n",
+                "function": "decide",
+                "arguments": ["input[2]"],
+            },
+            "actions": ["Notify"],
+        }))
+        .unwrap();
+        manifest.check().unwrap();
+
+        let mut monitor = Monitor::new(ledger.clone());
+        let subscription = Subscription::new(EventManifests::new(vec![manifest.clone()])).unwrap();
+        monitor.add(subscription.clone()).unwrap();
+        let transactions = ledger.get_transactions(height).unwrap();
+        let payloads = monitor
+            .transition_events(&subscription, &manifest, height, &transactions)
+            .unwrap();
+        assert_eq!(payloads.len(), 1);
+        let EventDetails::Transition(details) = payloads[0].details() else {
+            panic!("expected a transition event");
+        };
+        assert_eq!(
+            details.transaction(),
+            &transactions.iter().nth(1).unwrap().id()
+        );
+    }
+
+    #[test]
+    fn test_decision_programs_are_scoped_to_subscriptions() {
+        let rng = &mut TestRng::default();
+        let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let ledger = sample_ledger(&private_key, rng);
+        let mut monitor = Monitor::new(ledger.clone());
+
+        // Two subscriptions use different programs under the same name.
+        let subscribe = |comparison: &str| {
+            let manifest = serde_json::from_value(serde_json::json!({
+                "name": "genesisTransfers",
+                "description": "",
+                "program": "credits.aleo",
+                "function": "transfer_public_to_private",
+                "decision": {
+                    "program": format!("program decision_test.aleo;\n\nfunction decide:\n    input r0 as u64.public;\n    {comparison} r0 0u64 into r1;\n    output r1 as boolean.public;\n"),
+                    "function": "decide",
+                    "arguments": ["input[1]"],
+                },
+                "actions": ["Notify"],
+            }))
+            .unwrap();
+            Subscription::new(EventManifests::new(vec![manifest])).unwrap()
+        };
+        let accepting = subscribe("gt");
+        let rejecting = subscribe("lt");
+        monitor.add(accepting.clone()).unwrap();
+        monitor.add(rejecting.clone()).unwrap();
+
+        // Each subscription decides with its own program.
+        let transactions = ledger.get_transactions(0).unwrap();
+        let events = |subscription: &Subscription<CurrentNetwork>| {
+            monitor
+                .transition_events(subscription, &subscription.events()[0], 0, &transactions)
+                .unwrap()
+        };
+        assert_eq!(events(&accepting).len(), 4);
+        assert!(events(&rejecting).is_empty());
+
+        // A subscription that was never added has no decision programs loaded.
+        assert!(events(&subscribe("gt")).is_empty());
+
+        // A program that does not type-check is rejected when subscribing.
+        let manifest = serde_json::from_value(serde_json::json!({
+            "name": "mistyped",
+            "description": "",
+            "program": "credits.aleo",
+            "decision": {
+                "program": "program mistyped.aleo;\n\nfunction decide:\n    input r0 as u64.public;\n    is.eq r0 0u8 into r1;\n    output r1 as boolean.public;\n",
+                "function": "decide",
+            },
+            "actions": ["Notify"],
+        }))
+        .unwrap();
+        let mistyped = Subscription::new(EventManifests::new(vec![manifest])).unwrap();
+        assert!(monitor.add(mistyped).is_err());
+    }
+}
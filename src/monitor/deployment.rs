@@ -0,0 +1,180 @@
+use super::Monitor;
+use crate::{DeploymentEvent, DeploymentFilter, EventDetails, EventManifest, EventPayLoad};
+use anyhow::Result;
+use snarkvm::ledger::block::{ConfirmedTransaction, Transactions};
+use snarkvm::ledger::store::ConsensusStorage;
+use snarkvm::prelude::Network;
+
+impl<N: Network, C: ConsensusStorage<N>> Monitor<N, C> {
+    /// Find the program deployments in a block that match the manifest.
+    pub(crate) fn deployment_events(
+        &self,
+        event: &EventManifest<N>,
+        filter: &DeploymentFilter<N>,
+        height: u32,
+        transactions: &Transactions<N>,
+    ) -> Result<Vec<EventPayLoad<N>>> {
+        let mut payloads = vec![];
+        for transaction in transactions.iter() {
+            let (transaction_id, status) = Self::confirmation(transaction)?;
+            let fee = Self::fee(transaction)?;
+            if !event.status.includes(status) || !event.matches_fee(fee.as_ref()) {
+                continue;
+            }
+            // A rejected deployment is stored as a fee transaction holding the deployment.
+            let deployment = match transaction {
+                ConfirmedTransaction::RejectedDeploy(_, _, rejected, _) => rejected.deployment(),
+                _ => transaction.deployment(),
+            };
+            let Some(deployment) = deployment else {
+                continue;
+            };
+            let program = deployment.program();
+            if !event
+                .program
+                .as_ref()
+                .is_none_or(|pattern| pattern.matches(program.id()))
+            {
+                continue;
+            }
+            let imports = program.imports().keys().copied().collect::<Vec<_>>();
+            let functions = program.functions().keys().copied().collect::<Vec<_>>();
+            if !filter.matches(&imports, &functions) {
+                continue;
+            }
+            let details = DeploymentEvent::new(
+                *program.id(),
+                transaction_id,
+                status,
+                imports,
+                functions,
+                program.mappings().keys().copied().collect(),
+            );
+            payloads.push(EventPayLoad::new(
+                event.name.clone(),
+                event.description.clone(),
+                height,
+                fee,
+                EventDetails::Deployment(details),
+            ));
+        }
+        Ok(payloads)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_helpers::{advance, sample_ledger, unspent_records, CurrentNetwork};
+    use super::*;
+    use crate::EventKind;
+    use serde_json::json;
+    use snarkvm::prelude::{PrivateKey, TestRng};
+    use snarkvm::synthesizer::program::Program;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_deployment_events() {
+        let rng = &mut TestRng::default();
+        let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let ledger = sample_ledger(&private_key, rng);
+
+        let program = Program::<CurrentNetwork>::from_str(
+            r"import credits.aleo;
+program monitor_test.aleo;
+
+mapping pings:
+    key as address.public;
+    value as u64.public;
+
+function ping:
+    input r0 as u64.public;
+    async ping self.caller r0 into r1;
+    output r1 as monitor_test.aleo/ping.future;
+
+finalize ping:
+    input r0 as address.public;
+    input r1 as u64.public;
+    set r1 into pings[r0];",
+        )
+        .unwrap();
+        let transaction = ledger
+            .vm()
+            .deploy(
+                &private_key,
+                &program,
+                unspent_records(&ledger, &private_key).pop(),
+                0,
+                None,
+                rng,
+            )
+            .unwrap();
+        let deployed = transaction.id();
+        let height = advance(&ledger, &private_key, vec![transaction], rng);
+
+        let monitor = Monitor::new(ledger.clone());
+        let transactions = ledger.get_transactions(height).unwrap();
+        let deployment_events = |manifest: serde_json::Value| {
+            let manifest: EventManifest<CurrentNetwork> = serde_json::from_value(manifest).unwrap();
+            manifest.check().unwrap();
+            let EventKind::Deployment(filter) = &manifest.kind else {
+                unreachable!();
+            };
+            monitor
+                .deployment_events(&manifest, filter, height, &transactions)
+                .unwrap()
+        };
+
+        let payloads = deployment_events(json!({
+            "name": "creditsUsers",
+            "description": "Programs importing credits.aleo",
+            "program": "monitor_*",
+            "kind": {"Deployment": {"imports": ["credits.aleo"], "functions": ["ping"]}},
+            "actions": ["Notify"],
+        }));
+        assert_eq!(payloads.len(), 1);
+        assert_eq!(payloads[0].details().transactions(), vec![deployed]);
+        let details = serde_json::to_value(payloads[0].details()).unwrap();
+        assert_eq!(
+            details["Deployment"],
+            json!({
+                "program": "monitor_test.aleo",
+                "transaction": deployed.to_string(),
+                "status": "Accepted",
+                "imports": ["credits.aleo"],
+                "functions": ["ping"],
+                "mappings": ["pings"],
+            })
+        );
+        // The deployment fee was paid with a record.
+        let fee = payloads[0].fee().unwrap();
+        assert!(fee.base_amount() > 0);
+        assert_eq!(fee.priority_amount(), 0);
+        assert!(!fee.is_public());
+
+        // Other programs, missing functions and unmet fee conditions are not reported.
+        for manifest in [
+            json!({
+                "name": "tokens",
+                "description": "",
+                "program": "token_*",
+                "kind": {"Deployment": {}},
+                "actions": ["Notify"],
+            }),
+            json!({
+                "name": "pongs",
+                "description": "",
+                "kind": {"Deployment": {"functions": ["pong"]}},
+                "actions": ["Notify"],
+            }),
+            json!({
+                "name": "cheap",
+                "description": "",
+                "kind": {"Deployment": {}},
+                "fee": {"total": {"Lt": "1u64"}},
+                "actions": ["Notify"],
+            }),
+        ] {
+            assert!(deployment_events(manifest).is_empty());
+        }
+    }
+}
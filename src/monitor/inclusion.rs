@@ -0,0 +1,30 @@
+use super::Monitor;
+use crate::InclusionProof;
+use anyhow::Result;
+use snarkvm::ledger::block::{ConfirmedTransaction, Transition};
+use snarkvm::ledger::store::ConsensusStorage;
+use snarkvm::prelude::Network;
+
+impl<N: Network, C: ConsensusStorage<N>> Monitor<N, C> {
+    /// Build a proof that the transition is in the block at the height. Transitions of a rejected
+    /// execution are not stored in the block, so they have no proof.
+    pub(super) fn inclusion_proof(
+        &self,
+        height: u32,
+        transaction: &ConfirmedTransaction<N>,
+        transition: &Transition<N>,
+    ) -> Result<Option<InclusionProof<N>>> {
+        if !transaction
+            .transitions()
+            .any(|stored| stored.id() == transition.id())
+        {
+            return Ok(None);
+        }
+        let block = self.ledger.get_block(height)?;
+        Ok(Some(InclusionProof::prove(
+            &block,
+            transaction,
+            transition.id(),
+        )?))
+    }
+}
@@ -0,0 +1,472 @@
+use super::Monitor;
+use crate::events::selector::{future_plaintexts, input_plaintext, output_plaintext};
+use crate::{
+    EventDetails, EventManifest, EventPayLoad, MappingEvent, MappingFilter, MappingOperation,
+    Predicate,
+};
+use anyhow::Result;
+use snarkvm::ledger::block::{Output, Transaction, Transactions};
+use snarkvm::ledger::store::ConsensusStorage;
+use snarkvm::prelude::{Field, Identifier, Network, Plaintext, ProgramID, ToBits, Value};
+use snarkvm::synthesizer::program::FinalizeOperation;
+
+/// The values the monitor has seen for a mapping entry.
+#[derive(Clone, Debug)]
+pub(crate) struct MappingEntry<N: Network> {
+    // Block height, transaction index and operation index of the last update.
+    updated_at: (u32, usize, usize),
+    // Value before the last update.
+    previous: Option<Plaintext<N>>,
+    // Value after the last update.
+    current: Option<Plaintext<N>>,
+}
+
+/// Number of mapping entries whose values the monitor remembers. The entries seen first are
+/// forgotten first.
+const MAX_MAPPING_ENTRIES: usize = 100_000;
+
+impl<N: Network, C: ConsensusStorage<N>> Monitor<N, C> {
+    /// Find the finalize updates in a block to the manifest's mapping.
+    pub(crate) fn mapping_events(
+        &self,
+        event: &EventManifest<N>,
+        filter: &MappingFilter<N>,
+        height: u32,
+        transactions: &Transactions<N>,
+    ) -> Result<Vec<EventPayLoad<N>>> {
+        let mapping_id = to_mapping_id(&filter.program, &filter.mapping)?;
+        let mut payloads = vec![];
+        for (transaction_index, transaction) in transactions.iter().enumerate() {
+            let (transaction_id, status) = Self::confirmation(transaction)?;
+            let fee = Self::fee(transaction)?;
+            let mut candidates = None;
+            for (operation_index, operation) in transaction.finalize_operations().iter().enumerate()
+            {
+                let (operation, key_id, value_id) = match operation {
+                    FinalizeOperation::InsertKeyValue(id, key_id, value_id)
+                        if *id == mapping_id =>
+                    {
+                        (MappingOperation::Insert, *key_id, Some(*value_id))
+                    }
+                    FinalizeOperation::UpdateKeyValue(id, key_id, value_id)
+                        if *id == mapping_id =>
+                    {
+                        (MappingOperation::Update, *key_id, Some(*value_id))
+                    }
+                    FinalizeOperation::RemoveKeyValue(id, key_id) if *id == mapping_id => {
+                        (MappingOperation::Remove, *key_id, None)
+                    }
+                    _ => continue,
+                };
+
+                let candidates =
+                    candidates.get_or_insert_with(|| transaction_plaintexts(transaction));
+                let key = recover_key(filter, candidates, key_id)?;
+                // Only entries the filter selects by key are remembered.
+                if !matches_optional(filter.key.as_ref(), key.as_ref()) {
+                    continue;
+                }
+                let new_value = match (&key, value_id) {
+                    (Some(key), Some(value_id)) => {
+                        self.recover_value(filter, key, candidates, value_id)?
+                    }
+                    _ => None,
+                };
+                let old_value = self.apply_update(
+                    key_id,
+                    (height, transaction_index, operation_index),
+                    new_value.clone(),
+                );
+
+                if !event.status.includes(status)
+                    || !filter.includes(operation)
+                    || !event.matches_fee(fee.as_ref())
+                    || !matches_optional(filter.value.as_ref(), new_value.as_ref())
+                {
+                    continue;
+                }
+                let details = MappingEvent::new(
+                    filter.program,
+                    filter.mapping,
+                    operation,
+                    transaction_id,
+                    key,
+                    old_value,
+                    new_value,
+                );
+                payloads.push(EventPayLoad::new(
+                    event.name.clone(),
+                    event.description.clone(),
+                    height,
+                    fee,
+                    EventDetails::Mapping(details),
+                ));
+            }
+        }
+        Ok(payloads)
+    }
+
+    /// Recover the value behind a value ID. The value stored in the ledger is tried first, then
+    /// the operands of the value predicate and the public values and future arguments of the
+    /// transaction. Values computed in finalize that appear in none of them stay unknown.
+    fn recover_value(
+        &self,
+        filter: &MappingFilter<N>,
+        key: &Plaintext<N>,
+        candidates: &[Plaintext<N>],
+        value_id: Field<N>,
+    ) -> Result<Option<Plaintext<N>>> {
+        let stored = match self.ledger.vm().finalize_store().get_value_confirmed(
+            filter.program,
+            filter.mapping,
+            key,
+        )? {
+            Some(Value::Plaintext(value)) => Some(value),
+            _ => None,
+        };
+        for candidate in stored
+            .iter()
+            .chain(predicate_operands(filter.value.as_ref()))
+            .chain(candidates)
+        {
+            if to_value_id(&filter.program, &filter.mapping, key, candidate)? == value_id {
+                return Ok(Some(candidate.clone()));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Record the new value of an entry and return the value it replaced, if known. Updates are
+    /// applied once per position so every manifest watching the mapping sees the same old value.
+    fn apply_update(
+        &self,
+        key_id: Field<N>,
+        position: (u32, usize, usize),
+        new_value: Option<Plaintext<N>>,
+    ) -> Option<Plaintext<N>> {
+        let mut entries = self.mapping_entries.lock();
+        match entries.get_mut(&key_id) {
+            Some(entry) if entry.updated_at == position => entry.previous.clone(),
+            Some(entry) => {
+                entry.updated_at = position;
+                entry.previous = std::mem::replace(&mut entry.current, new_value);
+                entry.previous.clone()
+            }
+            None => {
+                if entries.len() >= MAX_MAPPING_ENTRIES {
+                    entries.shift_remove_index(0);
+                }
+                let entry = MappingEntry {
+                    updated_at: position,
+                    previous: None,
+                    current: new_value,
+                };
+                entries.insert(key_id, entry);
+                None
+            }
+        }
+    }
+}
+
+/// Returns `true` if there is no predicate, or the value is known and satisfies it.
+fn matches_optional<N: Network>(
+    predicate: Option<&Predicate<N>>,
+    value: Option<&Plaintext<N>>,
+) -> bool {
+    predicate.is_none_or(|predicate| value.is_some_and(|value| predicate.evaluate(value)))
+}
+
+/// Returns the values an `Eq` or `In` predicate lists.
+fn predicate_operands<N: Network>(predicate: Option<&Predicate<N>>) -> &[Plaintext<N>] {
+    match predicate {
+        Some(Predicate::Eq(value)) => std::slice::from_ref(value),
+        Some(Predicate::In(values)) => values,
+        _ => &[],
+    }
+}
+
+/// Returns the public values and future arguments of the transaction's transitions. Finalize
+/// operations only carry hashes, so keys and values are recovered from these.
+fn transaction_plaintexts<N: Network>(transaction: &Transaction<N>) -> Vec<Plaintext<N>> {
+    let mut plaintexts = vec![];
+    for transition in transaction.transitions() {
+        plaintexts.extend(
+            transition
+                .inputs()
+                .iter()
+                .filter_map(input_plaintext)
+                .cloned(),
+        );
+        for output in transition.outputs() {
+            match output {
+                Output::Future(_, Some(future)) => plaintexts.extend(
+                    future_plaintexts(future)
+                        .into_iter()
+                        .map(|(_, plaintext)| plaintext.clone()),
+                ),
+                output => plaintexts.extend(output_plaintext(output).cloned()),
+            }
+        }
+    }
+    plaintexts
+}
+
+/// Recover the key behind a key ID from the operands of the key predicate and the transaction's
+/// values. Keys listed by `Eq` or `In` are always recoverable.
+fn recover_key<N: Network>(
+    filter: &MappingFilter<N>,
+    candidates: &[Plaintext<N>],
+    key_id: Field<N>,
+) -> Result<Option<Plaintext<N>>> {
+    for candidate in predicate_operands(filter.key.as_ref())
+        .iter()
+        .chain(candidates)
+    {
+        if to_key_id(&filter.program, &filter.mapping, candidate)? == key_id {
+            return Ok(Some(candidate.clone()));
+        }
+    }
+    Ok(None)
+}
+
+// The IDs below are derived the same way snarkVM's finalize store derives them.
+
+/// Returns the mapping ID for the given program ID and mapping name.
+fn to_mapping_id<N: Network>(
+    program_id: &ProgramID<N>,
+    mapping_name: &Identifier<N>,
+) -> Result<Field<N>> {
+    let mut preimage = Vec::new();
+    program_id.write_bits_le(&mut preimage);
+    false.write_bits_le(&mut preimage); // Separator
+    mapping_name.write_bits_le(&mut preimage);
+    N::hash_bhp1024(&preimage)
+}
+
+/// Returns the key ID for the given program ID, mapping name and key.
+fn to_key_id<N: Network>(
+    program_id: &ProgramID<N>,
+    mapping_name: &Identifier<N>,
+    key: &Plaintext<N>,
+) -> Result<Field<N>> {
+    let mut preimage = Vec::new();
+    program_id.write_bits_le(&mut preimage);
+    false.write_bits_le(&mut preimage); // Separator
+    mapping_name.write_bits_le(&mut preimage);
+    false.write_bits_le(&mut preimage); // Separator
+    key.write_bits_le(&mut preimage);
+    N::hash_bhp1024(&preimage)
+}
+
+/// Returns the value ID for the given program ID, mapping name, key and value.
+fn to_value_id<N: Network>(
+    program_id: &ProgramID<N>,
+    mapping_name: &Identifier<N>,
+    key: &Plaintext<N>,
+    value: &Plaintext<N>,
+) -> Result<Field<N>> {
+    let mut preimage = Vec::new();
+    program_id.write_bits_le(&mut preimage);
+    false.write_bits_le(&mut preimage); // Separator
+    mapping_name.write_bits_le(&mut preimage);
+    false.write_bits_le(&mut preimage); // Separator
+    key.write_bits_le(&mut preimage);
+    false.write_bits_le(&mut preimage); // Separator
+    value.write_bits_le(&mut preimage);
+    N::hash_bhp1024(&preimage)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_helpers::{advance, sample_ledger, unspent_records, CurrentNetwork};
+    use super::*;
+    use crate::EventKind;
+    use serde_json::json;
+    use snarkvm::prelude::{Address, PrivateKey, TestRng};
+    use std::str::FromStr;
+
+    #[test]
+    fn test_mapping_events() {
+        let rng = &mut TestRng::default();
+        let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let address = Address::try_from(&private_key).unwrap();
+        let ledger = sample_ledger(&private_key, rng);
+        let monitor = Monitor::new(ledger.clone());
+
+        let account = |name: &str, filter: serde_json::Value| {
+            let manifest: EventManifest<CurrentNetwork> = serde_json::from_value(json!({
+                "name": name,
+                "description": "",
+                "kind": {"Mapping": filter},
+                "actions": ["Notify"],
+            }))
+            .unwrap();
+            manifest.check().unwrap();
+            manifest
+        };
+        let balance = |owner: &Address<CurrentNetwork>| {
+            let value = ledger
+                .vm()
+                .finalize_store()
+                .get_value_confirmed(
+                    ProgramID::from_str("credits.aleo").unwrap(),
+                    Identifier::from_str("account").unwrap(),
+                    &Plaintext::from_str(&owner.to_string()).unwrap(),
+                )
+                .unwrap()
+                .unwrap();
+            json!(value.to_string())
+        };
+        let mapping_events = |manifest: &EventManifest<CurrentNetwork>, height: u32| {
+            let EventKind::Mapping(filter) = &manifest.kind else {
+                unreachable!();
+            };
+            let transactions = ledger.get_transactions(height).unwrap();
+            monitor
+                .mapping_events(manifest, filter, height, &transactions)
+                .unwrap()
+                .iter()
+                .map(|payload| serde_json::to_value(payload.details()).unwrap()["Mapping"].clone())
+                .collect::<Vec<_>>()
+        };
+
+        // Each genesis transfer to a private balance lowers the caller's public balance. The
+        // balances between the transfers are recovered from the value predicate's operands.
+        let genesis_balance = balance(&address);
+        let amounts = ledger
+            .get_transactions(0)
+            .unwrap()
+            .iter()
+            .flat_map(|transaction| transaction.transitions())
+            .map(|transition| {
+                let amount = input_plaintext(&transition.inputs()[1]).unwrap();
+                u64::from_str(amount.to_string().trim_end_matches("u64")).unwrap()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(amounts.len(), 4);
+        let final_balance =
+            u64::from_str(genesis_balance.as_str().unwrap().trim_end_matches("u64")).unwrap();
+        let balances = (0..4)
+            .map(|i| {
+                format!(
+                    "{}u64",
+                    final_balance + amounts[i + 1..].iter().sum::<u64>()
+                )
+            })
+            .collect::<Vec<_>>();
+        let withdrawals = account(
+            "withdrawals",
+            json!({
+                "program": "credits.aleo",
+                "mapping": "account",
+                "operations": ["Update"],
+                "key": {"Eq": address.to_string()},
+                "value": {"In": balances},
+            }),
+        );
+        let updates = mapping_events(&withdrawals, 0);
+        assert_eq!(updates.len(), 4);
+        for (update, balance) in updates.iter().zip(&balances) {
+            assert_eq!(update["operation"], "Update");
+            assert_eq!(update["key"], address.to_string());
+            assert_eq!(update["new_value"], balance.as_str());
+        }
+        assert_eq!(updates[3]["new_value"], genesis_balance);
+
+        // Updates of keys the filter does not select are not remembered.
+        assert_eq!(monitor.mapping_entries.lock().len(), 1);
+
+        // Accepted transactions are left out when only rejected ones are reported.
+        let rejected: EventManifest<CurrentNetwork> = serde_json::from_value(json!({
+            "name": "rejected",
+            "description": "",
+            "kind": {"Mapping": {"program": "credits.aleo", "mapping": "account"}},
+            "status": "Rejected",
+            "actions": ["Notify"],
+        }))
+        .unwrap();
+        assert!(mapping_events(&rejected, 0).is_empty());
+
+        // A public transfer, with the fee paid by a record.
+        let receiver = Address::try_from(&PrivateKey::new(rng).unwrap()).unwrap();
+        let transfer = |receiver: &Address<CurrentNetwork>, fee_record, rng: &mut TestRng| {
+            let inputs = [
+                Value::from_str(&receiver.to_string()).unwrap(),
+                Value::from_str("1000u64").unwrap(),
+            ];
+            ledger
+                .vm()
+                .execute(
+                    &private_key,
+                    ("credits.aleo", "transfer_public"),
+                    inputs.into_iter(),
+                    Some(fee_record),
+                    0,
+                    None,
+                    rng,
+                )
+                .unwrap()
+        };
+        let mut fee_records = unspent_records(&ledger, &private_key);
+        let transaction = transfer(&receiver, fee_records.pop().unwrap(), rng);
+        let transaction_id = transaction.id();
+        let height = advance(&ledger, &private_key, vec![transaction], rng);
+
+        // The monitor remembers the balance it saw last, and reports the confirmed transaction.
+        let withdrawals = account(
+            "withdrawals",
+            json!({
+                "program": "credits.aleo",
+                "mapping": "account",
+                "operations": ["Update"],
+                "key": {"Eq": address.to_string()},
+            }),
+        );
+        let updates = mapping_events(&withdrawals, height);
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0]["transaction"], transaction_id.to_string());
+        assert_eq!(updates[0]["old_value"], genesis_balance);
+        assert_eq!(updates[0]["new_value"], balance(&address));
+        // The receiver's first deposit inserts its entry, and its key is recovered from the
+        // transfer's inputs.
+        let deposits = account(
+            "deposits",
+            json!({
+                "program": "credits.aleo",
+                "mapping": "account",
+                "operations": ["Insert"],
+                "value": {"Gte": "1000u64"},
+            }),
+        );
+        let inserts = mapping_events(&deposits, height);
+        assert_eq!(inserts.len(), 1);
+        assert_eq!(inserts[0]["key"], receiver.to_string());
+        assert!(inserts[0]["old_value"].is_null());
+        assert_eq!(inserts[0]["new_value"], "1000u64");
+
+        // Two deposits to a new account in one block. The stored value is the second balance,
+        // the first is recovered from the transfer's future arguments.
+        let receiver = Address::try_from(&PrivateKey::new(rng).unwrap()).unwrap();
+        let transactions = vec![
+            transfer(&receiver, fee_records.pop().unwrap(), rng),
+            transfer(&receiver, fee_records.pop().unwrap(), rng),
+        ];
+        let height = advance(&ledger, &private_key, transactions, rng);
+        let deposits = account(
+            "deposits",
+            json!({
+                "program": "credits.aleo",
+                "mapping": "account",
+                "key": {"Eq": receiver.to_string()},
+            }),
+        );
+        let updates = mapping_events(&deposits, height);
+        assert_eq!(updates.len(), 2);
+        assert_eq!(updates[0]["operation"], "Insert");
+        assert!(updates[0]["old_value"].is_null());
+        assert_eq!(updates[0]["new_value"], "1000u64");
+        assert_eq!(updates[1]["operation"], "Update");
+        assert_eq!(updates[1]["old_value"], "1000u64");
+        assert_eq!(updates[1]["new_value"], "2000u64");
+    }
+}
@@ -1,14 +1,57 @@
-use crate::{EventPayLoad, Subscription, SubscriptionID};
+use crate::{
+    webhook_client, ConfirmationStatus, DeliveryStatus, EventKind, EventManifest, EventPayLoad,
+    FeeDetails, Subscription, SubscriptionID,
+};
+use anyhow::Result;
 use indexmap::IndexMap;
 use parking_lot::Mutex;
+use rand::thread_rng;
+use snarkvm::ledger::block::{ConfirmedTransaction, Transactions};
 use snarkvm::ledger::store::ConsensusStorage;
 use snarkvm::ledger::Ledger;
-use snarkvm::prelude::{Itertools, Network};
-use std::sync::atomic::{AtomicU32, Ordering};
+use snarkvm::prelude::{Address, Field, Itertools, Network, PrivateKey};
+use snarkvm::synthesizer::Process;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
 use std::sync::Arc;
 use tokio::task::JoinHandle;
 use tokio::time::{sleep, Duration};
-use tracing::info;
+use tracing::{info, warn};
+
+mod abi;
+
+mod aborted;
+
+mod aggregate;
+use aggregate::AggregateWindow;
+
+mod block;
+
+mod committee;
+
+mod cast;
+
+mod composite;
+
+mod decision;
+use decision::{DecisionProcess, Verdict};
+
+mod deployment;
+
+mod inclusion;
+
+mod mapping;
+use mapping::MappingEntry;
+
+mod record;
+
+mod sequence;
+use sequence::PartialSequences;
+
+mod transition;
+
+mod verification;
+
+mod webhook;
 
 #[derive(Clone)]
 pub struct Monitor<N: Network, C: ConsensusStorage<N>> {
@@ -18,6 +61,38 @@ pub struct Monitor<N: Network, C: ConsensusStorage<N>> {
     #[allow(clippy::type_complexity)]
     matching_events: Arc<Mutex<IndexMap<SubscriptionID<N>, Vec<EventPayLoad<N>>>>>,
     join_handles: Arc<Mutex<Vec<JoinHandle<()>>>>,
+    // Values seen for each watched mapping entry, keyed by its key ID.
+    mapping_entries: Arc<Mutex<IndexMap<Field<N>, MappingEntry<N>>>>,
+    // Unspent records watched by each spent record manifest, as record tags to commitments.
+    #[allow(clippy::type_complexity)]
+    watched_records:
+        Arc<Mutex<IndexMap<(SubscriptionID<N>, String), IndexMap<Field<N>, Field<N>>>>>,
+    // Partially matched sequences of each sequence manifest.
+    #[allow(clippy::type_complexity)]
+    sequences: Arc<Mutex<IndexMap<(SubscriptionID<N>, String), PartialSequences<N>>>>,
+    // Current window of each aggregate manifest.
+    #[allow(clippy::type_complexity)]
+    aggregate_windows: Arc<Mutex<IndexMap<(SubscriptionID<N>, String), AggregateWindow<N>>>>,
+    // Scratch process holding only `credits.aleo`, loaded on first use and copied for each
+    // subscription with decision functions.
+    decision_process: Arc<Mutex<Option<DecisionProcess<N>>>>,
+    // Process each subscription's decision programs are loaded into.
+    decision_processes: Arc<Mutex<IndexMap<SubscriptionID<N>, DecisionProcess<N>>>>,
+    // Scratch process deployments are re-verified in, loaded on first use.
+    verifier: Arc<Mutex<Option<Process<N>>>>,
+    // Block height and verification results of its transactions, shared by paranoid
+    // subscriptions.
+    #[allow(clippy::type_complexity)]
+    verified: Arc<Mutex<(u32, IndexMap<N::TransactionID, Option<String>>)>>,
+    // Key that signs event payloads, and its address.
+    signer: Option<(PrivateKey<N>, Address<N>)>,
+    // Number given to the next recorded event.
+    next_event_id: Arc<AtomicU64>,
+    // Webhook deliveries of each subscription's latest events, keyed by event number.
+    #[allow(clippy::type_complexity)]
+    deliveries: Arc<Mutex<IndexMap<SubscriptionID<N>, IndexMap<u64, Vec<DeliveryStatus>>>>>,
+    // Client webhook requests are made with.
+    http: reqwest::Client,
 }
 
 impl<N: Network, C: ConsensusStorage<N>> Monitor<N, C> {
@@ -30,21 +105,53 @@ impl<N: Network, C: ConsensusStorage<N>> Monitor<N, C> {
             subscriptions: Arc::new(Mutex::new(Vec::new())),
             matching_events: Arc::new(Mutex::new(IndexMap::new())),
             join_handles: Arc::new(Mutex::new(Default::default())),
+            mapping_entries: Arc::new(Mutex::new(IndexMap::new())),
+            watched_records: Arc::new(Mutex::new(IndexMap::new())),
+            sequences: Arc::new(Mutex::new(IndexMap::new())),
+            aggregate_windows: Arc::new(Mutex::new(IndexMap::new())),
+            decision_process: Arc::new(Mutex::new(None)),
+            decision_processes: Arc::new(Mutex::new(IndexMap::new())),
+            verifier: Arc::new(Mutex::new(None)),
+            verified: Arc::new(Mutex::new((0, IndexMap::new()))),
+            signer: None,
+            next_event_id: Arc::new(AtomicU64::new(0)),
+            deliveries: Arc::new(Mutex::new(IndexMap::new())),
+            http: webhook_client(Vec::new()),
         }
     }
 
+    /// Sign every event payload with the private key.
+    pub fn with_private_key(mut self, private_key: PrivateKey<N>) -> Result<Self> {
+        self.signer = Some((private_key, Address::try_from(&private_key)?));
+        Ok(self)
+    }
+
+    /// Let webhooks post to the hosts even when they resolve to addresses that are not public,
+    /// such as services on the monitor's own network.
+    pub fn with_webhook_hosts(mut self, hosts: Vec<String>) -> Self {
+        self.http = webhook_client(hosts);
+        self
+    }
+
+    /// Get the address event payloads are signed by.
+    pub fn address(&self) -> Option<Address<N>> {
+        self.signer.map(|(_, address)| address)
+    }
+
     /// Get the ledger.
     pub fn ledger(&self) -> &Ledger<N, C> {
         &self.ledger
     }
 
-    /// Add subscription.
-    pub fn add(&mut self, subscription: Subscription<N>) {
+    /// Add subscription. Fails if one of its decision programs does not load.
+    pub fn add(&mut self, subscription: Subscription<N>) -> Result<()> {
         info!("Adding subscription {subscription:?}");
+        self.load_decisions(&subscription)?;
         self.matching_events
             .lock()
             .insert(*subscription.id(), vec![]);
-        self.subscriptions.lock().push(subscription)
+        self.subscriptions.lock().push(subscription);
+        Ok(())
     }
 
     /// Drain subscriptions.
@@ -71,46 +178,10 @@ impl<N: Network, C: ConsensusStorage<N>> Monitor<N, C> {
                 if self_.ledger.latest_height() > self_.latest_block.load(Ordering::Relaxed) {
                     for height in (latest_tracked_block + 1)..(latest_ledger_height + 1) {
                         info!("Getting events for height {height}");
-                        for subscription in self_.subscriptions.lock().iter() {
-                            let subscription_id = subscription.id();
-                            info!("Getting events for subscription id {subscription_id}");
-                            let transactions = self_.ledger.get_transactions(height).unwrap();
-                            for transaction in transactions.iter() {
-                                info!("Transaction {transaction:?} being searched");
-                                for transition in transaction.transitions() {
-                                    for event in subscription.events().iter() {
-                                        let program_id = &event.program;
-                                        let function_name = &event.function;
-                                        let transition_program = transition.program_id();
-                                        let transition_function = transition.function_name();
-                                        info!("Search program: {program_id}-Transition program: {transition_program}\nSearch function:{function_name}-Transition function:{transition_function}");
-                                        if transition.program_id() == program_id
-                                            && transition.function_name() == function_name
-                                        {
-                                            let payload = EventPayLoad::new(
-                                                event.name.clone(),
-                                                event.description.clone(),
-                                                *program_id,
-                                                height,
-                                                *function_name,
-                                                transaction.id(),
-                                                *transition.id(),
-                                                None,
-                                                None,
-                                            );
-                                            let subscription_id = subscription.id();
-                                            if let Some(payloads) = self_
-                                                .matching_events
-                                                .lock()
-                                                .get_mut(subscription_id)
-                                            {
-                                                info!("Adding event {payload:?} to subscription {subscription_id}");
-                                                payloads.push(payload);
-                                            }
-                                        }
-                                    }
-                                }
-                            }
+                        // Stop at the first block that fails so it is retried on the next pass.
+                        if let Err(error) = self_.process_block(height) {
+                            warn!("Failed to search block {height} for events: {error}");
+                            break;
                         }
                         self_.latest_block.store(height, Ordering::Relaxed);
                     }
@@ -120,4 +191,220 @@ impl<N: Network, C: ConsensusStorage<N>> Monitor<N, C> {
         });
         self.join_handles.lock().push(task);
     }
+
+    /// Search a block for events matching every subscription.
+    fn process_block(&self, height: u32) -> Result<()> {
+        let transactions = self.ledger.get_transactions(height)?;
+        // Searching can take a while with proven decisions, so subscribing is not held up by it.
+        let subscriptions = self.subscriptions.lock().clone();
+        for subscription in subscriptions.iter() {
+            let subscription_id = subscription.id();
+            info!("Getting events for subscription id {subscription_id}");
+            for event in subscription.events().iter() {
+                let payloads = self
+                    .event_payloads(subscription, event, height, &transactions)
+                    .and_then(|payloads| {
+                        if subscription.is_paranoid() {
+                            self.verify_payloads(payloads, height, &transactions)
+                        } else {
+                            Ok(payloads)
+                        }
+                    });
+                match payloads {
+                    Ok(payloads) => self.record(subscription_id, event, payloads),
+                    Err(error) => warn!("Failed to search for event '{}': {error}", event.name),
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Find the events in a block that match the manifest.
+    fn event_payloads(
+        &self,
+        subscription: &Subscription<N>,
+        event: &EventManifest<N>,
+        height: u32,
+        transactions: &Transactions<N>,
+    ) -> Result<Vec<EventPayLoad<N>>> {
+        match &event.kind {
+            EventKind::Transition => {
+                self.transition_events(subscription, event, height, transactions)
+            }
+            EventKind::Mapping(filter) => self.mapping_events(event, filter, height, transactions),
+            EventKind::Deployment(filter) => {
+                self.deployment_events(event, filter, height, transactions)
+            }
+            EventKind::Record => self.record_events(subscription, event, height, transactions),
+            EventKind::RecordSpent(filter) => {
+                self.spent_record_events(subscription, event, filter, height, transactions)
+            }
+            EventKind::Composite(filter) => {
+                self.composite_events(event, filter, height, transactions)
+            }
+            EventKind::Sequence(filter) => {
+                self.sequence_events(subscription, event, filter, height, transactions)
+            }
+            EventKind::Aggregate(filter) => {
+                self.aggregate_events(subscription, event, filter, height, transactions)
+            }
+            EventKind::Block(filter) => self.block_events(event, filter, height),
+            EventKind::Committee(filter) => self.committee_events(event, filter, height),
+            EventKind::Aborted => self.aborted_events(event, height),
+        }
+    }
+
+    /// Returns the ID a transaction was broadcast with and how it was confirmed. Rejected
+    /// transactions are stored as fee transactions under a different ID.
+    fn confirmation(
+        transaction: &ConfirmedTransaction<N>,
+    ) -> Result<(N::TransactionID, ConfirmationStatus)> {
+        if transaction.is_accepted() {
+            Ok((transaction.id(), ConfirmationStatus::Accepted))
+        } else {
+            Ok((
+                transaction.to_unconfirmed_transaction_id()?,
+                ConfirmationStatus::Rejected,
+            ))
+        }
+    }
+
+    /// Returns the fee a transaction paid, if it paid one.
+    fn fee(transaction: &ConfirmedTransaction<N>) -> Result<Option<FeeDetails>> {
+        match transaction.fee_transition() {
+            Some(fee) => Ok(Some(FeeDetails::new(
+                *fee.base_amount()?,
+                *fee.priority_amount()?,
+                fee.is_fee_public(),
+            ))),
+            None => Ok(None),
+        }
+    }
+
+    /// Store matched events for a subscription until they are drained, and post them to the
+    /// manifest's webhooks.
+    fn record(
+        &self,
+        subscription_id: &SubscriptionID<N>,
+        event: &EventManifest<N>,
+        payloads: Vec<EventPayLoad<N>>,
+    ) {
+        if let Some(events) = self.matching_events.lock().get_mut(subscription_id) {
+            for payload in payloads {
+                let id = self.next_event_id.fetch_add(1, Ordering::Relaxed);
+                let mut payload = payload.with_id(id);
+                info!("Adding event {payload:?} to subscription {subscription_id}");
+                if let Some((private_key, _)) = &self.signer {
+                    if let Err(error) = payload.sign(private_key, &mut thread_rng()) {
+                        warn!("Failed to sign event: {error}");
+                    }
+                }
+                self.deliver(subscription_id, event, &payload);
+                events.push(payload);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test_helpers {
+    use aleo_std::StorageMode;
+    use snarkvm::ledger::block::Transaction;
+    use snarkvm::ledger::store::helpers::memory::ConsensusMemory;
+    use snarkvm::ledger::store::ConsensusStore;
+    use snarkvm::ledger::{Ledger, RecordsFilter};
+    use snarkvm::prelude::{MainnetV0, Plaintext, PrivateKey, Record, TestRng, ViewKey};
+    use snarkvm::synthesizer::VM;
+
+    pub(crate) type CurrentNetwork = MainnetV0;
+    pub(crate) type CurrentLedger = Ledger<CurrentNetwork, ConsensusMemory<CurrentNetwork>>;
+
+    /// Start a ledger from a fresh genesis block. The private key gets the genesis records and
+    /// a committee seat, so it can produce the next blocks.
+    pub(crate) fn sample_ledger(
+        private_key: &PrivateKey<CurrentNetwork>,
+        rng: &mut TestRng,
+    ) -> CurrentLedger {
+        let store = ConsensusStore::<CurrentNetwork, ConsensusMemory<CurrentNetwork>>::open(
+            StorageMode::Production,
+        )
+        .unwrap();
+        let genesis = VM::from(store)
+            .unwrap()
+            .genesis_beacon(private_key, rng)
+            .unwrap();
+        CurrentLedger::load(genesis, StorageMode::Production).unwrap()
+    }
+
+    /// Returns the unspent records owned by the private key.
+    pub(crate) fn unspent_records(
+        ledger: &CurrentLedger,
+        private_key: &PrivateKey<CurrentNetwork>,
+    ) -> Vec<Record<CurrentNetwork, Plaintext<CurrentNetwork>>> {
+        let view_key = ViewKey::try_from(private_key).unwrap();
+        ledger
+            .find_records(&view_key, RecordsFilter::Unspent)
+            .unwrap()
+            .map(|(_, record)| record)
+            .collect()
+    }
+
+    /// Add a block holding the transactions and return its height.
+    pub(crate) fn advance(
+        ledger: &CurrentLedger,
+        private_key: &PrivateKey<CurrentNetwork>,
+        transactions: Vec<Transaction<CurrentNetwork>>,
+        rng: &mut TestRng,
+    ) -> u32 {
+        let block = ledger
+            .prepare_advance_to_next_beacon_block(private_key, vec![], vec![], transactions, rng)
+            .unwrap();
+        ledger.advance_to_next_block(&block).unwrap();
+        block.height()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::test_helpers::{sample_ledger, CurrentNetwork};
+    use super::*;
+    use crate::EventManifests;
+    use snarkvm::prelude::TestRng;
+
+    #[test]
+    fn test_recorded_events_are_signed() {
+        let rng = &mut TestRng::default();
+        let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let ledger = sample_ledger(&private_key, rng);
+
+        let signer = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let mut monitor = Monitor::new(ledger).with_private_key(signer).unwrap();
+        let address = monitor.address().unwrap();
+        assert_eq!(address, Address::try_from(&signer).unwrap());
+
+        // The genesis block holds transfers from public to private balances.
+        let manifest: EventManifest<CurrentNetwork> = serde_json::from_value(serde_json::json!({
+            "name": "genesisTransfers",
+            "description": "Genesis transfers",
+            "program": "credits.aleo",
+            "function": "transfer_public_to_private",
+            "actions": ["Notify"],
+        }))
+        .unwrap();
+        let subscription = Subscription::new(EventManifests::new(vec![manifest])).unwrap();
+        let id = *subscription.id();
+        monitor.add(subscription).unwrap();
+        monitor.process_block(0).unwrap();
+
+        let (_, events) = monitor.drain(id);
+        assert!(!events.is_empty());
+        for event in events {
+            assert!(event.verify(&address));
+            // A payload that went through a relay as JSON still verifies.
+            let relayed: EventPayLoad<CurrentNetwork> =
+                serde_json::from_str(&serde_json::to_string_pretty(&event).unwrap()).unwrap();
+            assert!(relayed.verify(&address));
+            assert!(!relayed.verify(&Address::try_from(&private_key).unwrap()));
+        }
+    }
 }
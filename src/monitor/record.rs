@@ -0,0 +1,421 @@
+use super::{Monitor, Verdict};
+use crate::{
+    EventDetails, EventManifest, EventPayLoad, RecordEvent, RecordSpentEvent, SpentFilter,
+    Subscription,
+};
+use anyhow::{anyhow, Result};
+use indexmap::{map::Entry, IndexMap};
+use snarkvm::ledger::block::{Input, Output, Transactions};
+use snarkvm::ledger::store::ConsensusStorage;
+use snarkvm::prelude::{Field, Network, Plaintext, Record};
+
+impl<N: Network, C: ConsensusStorage<N>> Monitor<N, C> {
+    /// Find the records owned by the subscription's view key in transitions that match the
+    /// manifest.
+    pub(crate) fn record_events(
+        &self,
+        subscription: &Subscription<N>,
+        event: &EventManifest<N>,
+        height: u32,
+        transactions: &Transactions<N>,
+    ) -> Result<Vec<EventPayLoad<N>>> {
+        let view_key = subscription
+            .view_key()
+            .ok_or_else(|| anyhow!("Record events require a view key"))?;
+        let mut payloads = vec![];
+        for transaction in transactions.iter() {
+            let (transaction_id, status) = Self::confirmation(transaction)?;
+            let fee = Self::fee(transaction)?;
+            if !event.status.includes(status) || !event.matches_fee(fee.as_ref()) {
+                continue;
+            }
+            // Only the stored transitions created records. For a rejected transaction that is
+            // the fee transition alone.
+            for transition in transaction.transitions() {
+                let owned = |output: &Output<N>| matches!(output, Output::Record(_, _, Some(record)) if record.is_owner(view_key));
+                if !transition.outputs().iter().any(owned) {
+                    continue;
+                }
+                let Verdict::Accepted(decision_proof) =
+                    self.verdict(subscription, event, transition, true)
+                else {
+                    continue;
+                };
+                let inclusion_proof = if event.inclusion_proof {
+                    self.inclusion_proof(height, transaction, transition)?
+                } else {
+                    None
+                };
+                for (index, output) in transition.outputs().iter().enumerate() {
+                    let Output::Record(commitment, _, Some(record)) = output else {
+                        continue;
+                    };
+                    if !record.is_owner(view_key) {
+                        continue;
+                    }
+                    let details = RecordEvent::new(
+                        *transition.program_id(),
+                        *transition.function_name(),
+                        transaction_id,
+                        *transition.id(),
+                        status,
+                        index as u32,
+                        *commitment,
+                        record.decrypt(view_key)?,
+                    );
+                    payloads.push(
+                        EventPayLoad::new(
+                            event.name.clone(),
+                            event.description.clone(),
+                            height,
+                            fee,
+                            EventDetails::Record(details),
+                        )
+                        .with_decision_proof(decision_proof.clone())
+                        .with_inclusion_proof(inclusion_proof.clone()),
+                    );
+                }
+            }
+        }
+        Ok(payloads)
+    }
+
+    /// Find the transitions that spend records watched by the manifest. A record is watched from
+    /// the start if its commitment is listed in the filter, or from the block it is created in if
+    /// it is owned by the subscription's view key. Spends are matched by record tag.
+    pub(crate) fn spent_record_events(
+        &self,
+        subscription: &Subscription<N>,
+        event: &EventManifest<N>,
+        filter: &SpentFilter<N>,
+        height: u32,
+        transactions: &Transactions<N>,
+    ) -> Result<Vec<EventPayLoad<N>>> {
+        let sk_tag = subscription
+            .graph_key()
+            .ok_or_else(|| anyhow!("Spent record events require a graph or view key"))?
+            .sk_tag();
+        let tag = |commitment: Field<N>| Record::<N, Plaintext<N>>::tag(sk_tag, commitment);
+
+        // The watch list is updated under the lock, and the spends are decided on and proven
+        // once it is released.
+        let mut spends = vec![];
+        {
+            let mut watched_records = self.watched_records.lock();
+            let records = match watched_records.entry((*subscription.id(), event.name.clone())) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => {
+                    let mut records = IndexMap::new();
+                    for commitment in filter.commitments.iter().flatten() {
+                        records.insert(tag(*commitment)?, *commitment);
+                    }
+                    entry.insert(records)
+                }
+            };
+            for transaction in transactions.iter() {
+                let (_, status) = Self::confirmation(transaction)?;
+                let fee = Self::fee(transaction)?;
+                let reportable = event.status.includes(status) && event.matches_fee(fee.as_ref());
+                for transition in transaction.transitions() {
+                    for input in transition.inputs() {
+                        let Input::Record(serial_number, record_tag) = input else {
+                            continue;
+                        };
+                        // A spent record is no longer watched, whether or not the spend is
+                        // reported.
+                        let Some(commitment) = records.swap_remove(record_tag) else {
+                            continue;
+                        };
+                        if reportable {
+                            spends.push((transaction, transition, commitment, *serial_number));
+                        }
+                    }
+                    let Some(view_key) = subscription.view_key() else {
+                        continue;
+                    };
+                    for output in transition.outputs() {
+                        if let Output::Record(commitment, _, Some(record)) = output {
+                            if record.is_owner(view_key) {
+                                records.insert(tag(*commitment)?, *commitment);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut payloads = vec![];
+        // Decided at the first watched spend of each transition, so once per transition.
+        let mut verdict = None;
+        for (transaction, transition, commitment, serial_number) in spends {
+            let (transaction_id, status) = Self::confirmation(transaction)?;
+            let fee = Self::fee(transaction)?;
+            if !verdict
+                .as_ref()
+                .is_some_and(|(id, _)| id == transition.id())
+            {
+                let decision = self.verdict(subscription, event, transition, true);
+                verdict = Some((*transition.id(), decision));
+            }
+            let Some((_, Verdict::Accepted(decision_proof))) = &verdict else {
+                continue;
+            };
+            let inclusion_proof = if event.inclusion_proof {
+                self.inclusion_proof(height, transaction, transition)?
+            } else {
+                None
+            };
+            let details = RecordSpentEvent::new(
+                *transition.program_id(),
+                *transition.function_name(),
+                transaction_id,
+                *transition.id(),
+                status,
+                commitment,
+                serial_number,
+            );
+            payloads.push(
+                EventPayLoad::new(
+                    event.name.clone(),
+                    event.description.clone(),
+                    height,
+                    fee,
+                    EventDetails::RecordSpent(details),
+                )
+                .with_decision_proof(decision_proof.clone())
+                .with_inclusion_proof(inclusion_proof),
+            );
+        }
+        Ok(payloads)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_helpers::{advance, sample_ledger, CurrentLedger, CurrentNetwork};
+    use super::*;
+    use crate::{EventKind, EventManifests};
+    use serde_json::json;
+    use snarkvm::ledger::block::Transaction;
+    use snarkvm::ledger::RecordsFilter;
+    use snarkvm::prelude::{Address, GraphKey, PrivateKey, TestRng, Value, ViewKey};
+    use std::str::FromStr;
+
+    #[test]
+    fn test_record_events() {
+        let rng = &mut TestRng::default();
+        let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let view_key = ViewKey::try_from(&private_key).unwrap();
+        let ledger = sample_ledger(&private_key, rng);
+        let monitor = Monitor::new(ledger.clone());
+        let transactions = ledger.get_transactions(0).unwrap();
+
+        let manifest: EventManifest<CurrentNetwork> = serde_json::from_value(json!({
+            "name": "genesisRecords",
+            "description": "Records created in the genesis block",
+            "kind": "Record",
+            "function": "transfer_public_to_private",
+            "actions": ["Notify"],
+        }))
+        .unwrap();
+        manifest.check().unwrap();
+
+        // Each genesis transfer gives the view key's owner one record.
+        let subscribe = |manifest: &EventManifest<CurrentNetwork>, view_key| {
+            let manifests = EventManifests::new(vec![manifest.clone()]).with_view_key(view_key);
+            Subscription::new(manifests).unwrap()
+        };
+        let owner = subscribe(&manifest, view_key);
+        let payloads = monitor
+            .record_events(&owner, &manifest, 0, &transactions)
+            .unwrap();
+        assert_eq!(payloads.len(), 4);
+        let mut commitments = ledger
+            .find_records(&view_key, RecordsFilter::Unspent)
+            .unwrap()
+            .map(|(commitment, _)| json!(commitment.to_string()))
+            .collect::<Vec<_>>();
+        for payload in &payloads {
+            let details = &serde_json::to_value(payload.details()).unwrap()["Record"];
+            assert_eq!(details["program"], "credits.aleo");
+            assert_eq!(details["function_id"], "transfer_public_to_private");
+            assert_eq!(details["status"], "Accepted");
+            let position = commitments
+                .iter()
+                .position(|commitment| *commitment == details["commitment"])
+                .expect("every reported record is one of the owner's records");
+            commitments.remove(position);
+        }
+
+        // Records owned by someone else are not decrypted.
+        let stranger = ViewKey::try_from(&PrivateKey::<CurrentNetwork>::new(rng).unwrap()).unwrap();
+        assert!(monitor
+            .record_events(&subscribe(&manifest, stranger), &manifest, 0, &transactions)
+            .unwrap()
+            .is_empty());
+
+        // Neither are records of transitions the manifest does not match.
+        let mut transfers = manifest;
+        transfers.function = Some("transfer_private".parse().unwrap());
+        assert!(monitor
+            .record_events(
+                &subscribe(&transfers, view_key),
+                &transfers,
+                0,
+                &transactions
+            )
+            .unwrap()
+            .is_empty());
+    }
+
+    /// Send part of a record back to its owner, paying the fee from the public balance.
+    fn spend(
+        ledger: &CurrentLedger,
+        private_key: &PrivateKey<CurrentNetwork>,
+        record: Record<CurrentNetwork, Plaintext<CurrentNetwork>>,
+        rng: &mut TestRng,
+    ) -> Transaction<CurrentNetwork> {
+        let address = Address::try_from(private_key).unwrap();
+        let inputs = [
+            Value::Record(record),
+            Value::from_str(&address.to_string()).unwrap(),
+            Value::from_str("1000u64").unwrap(),
+        ];
+        ledger
+            .vm()
+            .execute(
+                private_key,
+                ("credits.aleo", "transfer_private"),
+                inputs.into_iter(),
+                None,
+                0,
+                None,
+                rng,
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn test_spent_record_events() {
+        let rng = &mut TestRng::default();
+        let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let view_key = ViewKey::try_from(&private_key).unwrap();
+        let graph_key = GraphKey::try_from(view_key).unwrap();
+        let ledger = sample_ledger(&private_key, rng);
+        let monitor = Monitor::new(ledger.clone());
+
+        let genesis_records = ledger
+            .find_records(&view_key, RecordsFilter::Unspent)
+            .unwrap()
+            .collect::<Vec<_>>();
+        let (listed, listed_record) = genesis_records[0].clone();
+        let manifests = |name: &str, status: &str| {
+            let manifest = serde_json::from_value(json!({
+                "name": name,
+                "description": "",
+                "kind": {"RecordSpent": {"commitments": [listed.to_string()]}},
+                "status": status,
+                "actions": ["Notify"],
+            }))
+            .unwrap();
+            EventManifests::new(vec![manifest])
+        };
+        let spent_record_events = |subscription: &Subscription<CurrentNetwork>, height: u32| {
+            let event = &subscription.events()[0];
+            let EventKind::RecordSpent(filter) = &event.kind else {
+                unreachable!();
+            };
+            let transactions = ledger.get_transactions(height).unwrap();
+            monitor
+                .spent_record_events(subscription, event, filter, height, &transactions)
+                .unwrap()
+                .iter()
+                .map(|payload| {
+                    serde_json::to_value(payload.details()).unwrap()["RecordSpent"].clone()
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // Only the listed commitment is watched with a graph key. The view key also watches the
+        // records it receives, from the first block the manifest looks at.
+        let by_graph_key =
+            Subscription::new(manifests("byGraphKey", "Accepted").with_graph_key(graph_key))
+                .unwrap();
+        let by_view_key =
+            Subscription::new(manifests("byViewKey", "Accepted").with_view_key(view_key)).unwrap();
+        // Only spends in rejected transactions are reported.
+        let rejected =
+            Subscription::new(manifests("rejected", "Rejected").with_view_key(view_key)).unwrap();
+        for subscription in [&by_graph_key, &by_view_key, &rejected] {
+            assert!(spent_record_events(subscription, 0).is_empty());
+        }
+
+        // The listed record is spent.
+        let height = advance(
+            &ledger,
+            &private_key,
+            vec![spend(&ledger, &private_key, listed_record, rng)],
+            rng,
+        );
+        for subscription in [&by_graph_key, &by_view_key] {
+            let spends = spent_record_events(subscription, height);
+            assert_eq!(spends.len(), 1);
+            assert_eq!(spends[0]["function_id"], "transfer_private");
+            assert_eq!(spends[0]["commitment"], listed.to_string());
+            assert_eq!(spends[0]["status"], "Accepted");
+        }
+
+        // A spend the status filter leaves out still ends the watch.
+        assert!(spent_record_events(&rejected, height).is_empty());
+        let tag =
+            Record::<CurrentNetwork, Plaintext<CurrentNetwork>>::tag(graph_key.sk_tag(), listed)
+                .unwrap();
+        {
+            let watched = monitor.watched_records.lock();
+            let watched = &watched[&(*rejected.id(), "rejected".to_string())];
+            assert!(!watched.contains_key(&tag));
+            assert_eq!(watched.len(), genesis_records.len() + 1);
+        }
+
+        // A record received in the last block is spent in the next one. Only the view key
+        // watches it.
+        let (received, received_record) = ledger
+            .find_records(&view_key, RecordsFilter::Unspent)
+            .unwrap()
+            .find(|(commitment, _)| {
+                genesis_records
+                    .iter()
+                    .all(|(genesis, _)| genesis != commitment)
+            })
+            .unwrap();
+        let height = advance(
+            &ledger,
+            &private_key,
+            vec![spend(&ledger, &private_key, received_record, rng)],
+            rng,
+        );
+        assert!(spent_record_events(&by_graph_key, height).is_empty());
+        let spends = spent_record_events(&by_view_key, height);
+        assert_eq!(spends.len(), 1);
+        assert_eq!(spends[0]["commitment"], received.to_string());
+
+        // Spent records can only be found with a graph or view key.
+        let manifest = by_view_key.events()[0].clone();
+        assert!(Subscription::new(EventManifests::new(vec![manifest.clone()])).is_err());
+        let transfers: EventManifest<CurrentNetwork> = serde_json::from_value(json!({
+            "name": "transfers",
+            "description": "",
+            "function": "transfer_private",
+            "actions": ["Notify"],
+        }))
+        .unwrap();
+        let keyless = Subscription::new(EventManifests::new(vec![transfers])).unwrap();
+        let EventKind::RecordSpent(filter) = &manifest.kind else {
+            unreachable!();
+        };
+        let transactions = ledger.get_transactions(height).unwrap();
+        assert!(monitor
+            .spent_record_events(&keyless, &manifest, filter, height, &transactions)
+            .is_err());
+    }
+}
@@ -0,0 +1,373 @@
+use super::Monitor;
+use crate::{
+    EventDetails, EventManifest, EventPayLoad, SequenceEvent, SequenceFilter, SequenceOutcome,
+    SequenceStep, Subscription,
+};
+use anyhow::Result;
+use indexmap::IndexMap;
+use snarkvm::ledger::block::{ConfirmedTransaction, Transactions, Transition};
+use snarkvm::ledger::store::ConsensusStorage;
+use snarkvm::prelude::{Itertools, Network, Plaintext};
+use std::collections::VecDeque;
+
+/// A sequence whose first steps have matched.
+#[derive(Clone, Debug)]
+pub(crate) struct PartialSequence<N: Network> {
+    // Order in which the sequence was started.
+    number: u64,
+    // Steps matched so far.
+    steps: Vec<SequenceStep<N>>,
+    // Value of the correlation selector shared by every step.
+    key: Option<Plaintext<N>>,
+    // First step whose transaction failed verification, and why, if the subscription is
+    // paranoid.
+    failure: Option<(N::TransactionID, String)>,
+}
+
+impl<N: Network> PartialSequence<N> {
+    /// Block height of the first step.
+    fn started_at(&self) -> u32 {
+        self.steps[0].block_height()
+    }
+}
+
+/// The partial sequences of a manifest, grouped by the step they wait for and their correlation
+/// key, so a transition only looks at the sequences it could advance.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct PartialSequences<N: Network> {
+    // Sequences by the index of their next step and the string form of their key, oldest first.
+    waiting: IndexMap<(usize, Option<String>), VecDeque<PartialSequence<N>>>,
+    // Number of sequences started.
+    started: u64,
+}
+
+impl<N: Network> PartialSequences<N> {
+    /// Remove and return the sequences whose window closed before the height, oldest first.
+    fn expire(&mut self, height: u32, within: u32) -> Vec<PartialSequence<N>> {
+        let mut expired = vec![];
+        self.waiting.retain(|_, partials| {
+            partials.retain(|partial| {
+                let open = height <= partial.started_at().saturating_add(within);
+                if !open {
+                    expired.push(partial.clone());
+                }
+                open
+            });
+            !partials.is_empty()
+        });
+        expired.sort_by_key(|partial| partial.number);
+        expired
+    }
+
+    /// Remove and return the oldest sequence waiting for the transition as one of its steps.
+    fn take_waiting(
+        &mut self,
+        filter: &SequenceFilter<N>,
+        transition: &Transition<N>,
+    ) -> Option<PartialSequence<N>> {
+        let group = (1..filter.steps.len())
+            .filter_map(|index| {
+                let group = (
+                    index,
+                    key_string(&correlation_key(filter, index, transition)),
+                );
+                let number = self.waiting.get(&group)?.front()?.number;
+                Some((number, index, group))
+            })
+            .sorted_by_key(|(number, _, _)| *number)
+            .find(|(_, index, _)| filter.steps[*index].evaluate(transition))
+            .map(|(_, _, group)| group)?;
+        let partials = self.waiting.get_mut(&group)?;
+        let partial = partials.pop_front();
+        if partials.is_empty() {
+            self.waiting.shift_remove(&group);
+        }
+        partial
+    }
+
+    /// Start a sequence at its first step.
+    fn start(
+        &mut self,
+        step: SequenceStep<N>,
+        key: Option<Plaintext<N>>,
+        failure: Option<(N::TransactionID, String)>,
+    ) -> PartialSequence<N> {
+        self.started += 1;
+        PartialSequence {
+            number: self.started,
+            steps: vec![step],
+            key,
+            failure,
+        }
+    }
+
+    /// Put a sequence back to wait for its next step, behind the older sequences waiting for it.
+    fn wait(&mut self, partial: PartialSequence<N>) {
+        let group = (partial.steps.len(), key_string(&partial.key));
+        let partials = self.waiting.entry(group).or_default();
+        let position = partials.partition_point(|other| other.number < partial.number);
+        partials.insert(position, partial);
+    }
+}
+
+impl<N: Network, C: ConsensusStorage<N>> Monitor<N, C> {
+    /// Advance the manifest's partial sequences through a block. Each transition advances the
+    /// oldest sequence waiting for it, or starts a new sequence if it matches the first step.
+    /// For a paranoid subscription, each step's transaction is verified when it matches, and a
+    /// sequence with a step that failed is reported as a verification failure.
+    pub(crate) fn sequence_events(
+        &self,
+        subscription: &Subscription<N>,
+        event: &EventManifest<N>,
+        filter: &SequenceFilter<N>,
+        height: u32,
+        transactions: &Transactions<N>,
+    ) -> Result<Vec<EventPayLoad<N>>> {
+        // The sequences are taken out of the map so the lock is not held while steps are
+        // verified, and put back whether or not the block could be searched.
+        let key = (*subscription.id(), event.name.clone());
+        let mut partials = self.sequences.lock().swap_remove(&key).unwrap_or_default();
+        let payloads = self.advance_sequences(
+            subscription,
+            event,
+            filter,
+            &mut partials,
+            height,
+            transactions,
+        );
+        self.sequences.lock().insert(key, partials);
+        payloads
+    }
+
+    /// Advance the partial sequences through a block and return the sequences it completes or
+    /// times out.
+    fn advance_sequences(
+        &self,
+        subscription: &Subscription<N>,
+        event: &EventManifest<N>,
+        filter: &SequenceFilter<N>,
+        partials: &mut PartialSequences<N>,
+        height: u32,
+        transactions: &Transactions<N>,
+    ) -> Result<Vec<EventPayLoad<N>>> {
+        let mut payloads = vec![];
+        let emit = |payloads: &mut Vec<EventPayLoad<N>>,
+                    outcome,
+                    steps,
+                    failure: Option<(N::TransactionID, String)>| {
+            let payload = EventPayLoad::new(
+                event.name.clone(),
+                event.description.clone(),
+                height,
+                None,
+                EventDetails::Sequence(SequenceEvent::new(outcome, steps)),
+            );
+            payloads.push(match failure {
+                Some((transaction, error)) => payload.into_verification_failure(transaction, error),
+                None => payload,
+            })
+        };
+        let verify = |transaction: &ConfirmedTransaction<N>| match subscription.is_paranoid() {
+            true => self.verification_failure(height, transaction),
+            false => Ok(None),
+        };
+
+        // Expire sequences whose window closed before this block.
+        let expired = partials.expire(height, filter.within);
+        if filter.reports_timed_out() {
+            for partial in expired {
+                emit(
+                    &mut payloads,
+                    SequenceOutcome::TimedOut,
+                    partial.steps,
+                    partial.failure,
+                );
+            }
+        }
+
+        for transaction in transactions.iter() {
+            let (transaction_id, status) = Self::confirmation(transaction)?;
+            let fee = Self::fee(transaction)?;
+            if !event.status.includes(status) || !event.matches_fee(fee.as_ref()) {
+                continue;
+            }
+            for transition in Self::executed_transitions(transaction) {
+                let step = SequenceStep::new(height, transaction_id, *transition.id());
+                let partial = match partials.take_waiting(filter, transition) {
+                    Some(mut partial) => {
+                        partial.steps.push(step);
+                        if partial.failure.is_none() {
+                            partial.failure = verify(transaction)?;
+                        }
+                        partial
+                    }
+                    None if filter.steps[0].evaluate(transition) => {
+                        let key = correlation_key(filter, 0, transition);
+                        if filter.correlate.is_some() && key.is_none() {
+                            continue;
+                        }
+                        let failure = verify(transaction)?;
+                        partials.start(step, key, failure)
+                    }
+                    None => continue,
+                };
+                if partial.steps.len() < filter.steps.len() {
+                    partials.wait(partial);
+                } else if filter.reports_completed() {
+                    emit(
+                        &mut payloads,
+                        SequenceOutcome::Completed,
+                        partial.steps,
+                        partial.failure,
+                    );
+                }
+            }
+        }
+        Ok(payloads)
+    }
+}
+
+/// Returns the string form of a correlation key, which the partial sequences are grouped by.
+fn key_string<N: Network>(key: &Option<Plaintext<N>>) -> Option<String> {
+    key.as_ref().map(ToString::to_string)
+}
+
+/// Returns the value of the step's correlation selector, or `None` if the sequence is not
+/// correlated or the value is not public.
+fn correlation_key<N: Network>(
+    filter: &SequenceFilter<N>,
+    step: usize,
+    transition: &Transition<N>,
+) -> Option<Plaintext<N>> {
+    filter
+        .correlate
+        .as_ref()
+        .and_then(|correlate| correlate[step].select(transition))
+        .cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_helpers::{advance, sample_ledger, CurrentNetwork};
+    use super::*;
+    use crate::{EventKind, EventManifests};
+    use serde_json::json;
+    use snarkvm::prelude::{Address, PrivateKey, TestRng, Value};
+    use std::str::FromStr;
+
+    #[test]
+    fn test_sequence_events() {
+        let rng = &mut TestRng::default();
+        let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let ledger = sample_ledger(&private_key, rng);
+        let monitor = Monitor::new(ledger.clone());
+
+        // A payment of 1 microcredit followed by one of 2 to the same receiver within 2 blocks.
+        let manifest: EventManifest<CurrentNetwork> = serde_json::from_value(json!({
+            "name": "twoPayments",
+            "description": "",
+            "kind": {"Sequence": {
+                "steps": [
+                    {"All": [
+                        {"Function": "transfer_public"},
+                        {"Value": ["input[1]", {"Eq": "1u64"}]},
+                    ]},
+                    {"All": [
+                        {"Function": "transfer_public"},
+                        {"Value": ["input[1]", {"Eq": "2u64"}]},
+                    ]},
+                ],
+                "within": 2,
+                "correlate": ["input[0]", "input[0]"],
+                "report": "Both",
+            }},
+            "actions": ["Notify"],
+        }))
+        .unwrap();
+        let subscription = Subscription::new(EventManifests::new(vec![manifest])).unwrap();
+        let event = &subscription.events()[0];
+        let EventKind::Sequence(filter) = &event.kind else {
+            unreachable!();
+        };
+        let block = |payments: &[(Address<CurrentNetwork>, u64)], rng: &mut TestRng| {
+            let transactions = payments
+                .iter()
+                .map(|(receiver, amount)| {
+                    let inputs = [
+                        Value::from_str(&receiver.to_string()).unwrap(),
+                        Value::from_str(&format!("{amount}u64")).unwrap(),
+                    ];
+                    ledger
+                        .vm()
+                        .execute(
+                            &private_key,
+                            ("credits.aleo", "transfer_public"),
+                            inputs.into_iter(),
+                            None,
+                            0,
+                            None,
+                            rng,
+                        )
+                        .unwrap()
+                })
+                .collect::<Vec<_>>();
+            let height = advance(&ledger, &private_key, transactions, rng);
+            let transactions = ledger.get_transactions(height).unwrap();
+            let sequences = monitor
+                .sequence_events(&subscription, event, filter, height, &transactions)
+                .unwrap()
+                .iter()
+                .map(|payload| serde_json::to_value(payload.details()).unwrap()["Sequence"].clone())
+                .collect::<Vec<_>>();
+            let ids = transactions
+                .iter()
+                .map(|transaction| json!(transaction.id().to_string()))
+                .collect::<Vec<_>>();
+            (sequences, ids)
+        };
+        let [a, b, c, d] = [(); 4]
+            .map(|()| Address::try_from(&PrivateKey::<CurrentNetwork>::new(rng).unwrap()).unwrap());
+        let transaction_ids = |sequence: &serde_json::Value| {
+            sequence["steps"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|step| step["transaction"].clone())
+                .collect::<Vec<_>>()
+        };
+
+        // The first payments to A, B and D start sequences. The second payment to D comes before
+        // its first, so it starts nothing.
+        let (sequences, first) = block(&[(a, 1), (b, 1), (d, 2), (d, 1)], rng);
+        assert!(sequences.is_empty());
+
+        // The second payment to B completes its sequence. C has no sequence to complete.
+        let (sequences, second) = block(&[(b, 2), (c, 2)], rng);
+        assert_eq!(sequences.len(), 1);
+        assert_eq!(sequences[0]["outcome"], "Completed");
+        assert_eq!(
+            transaction_ids(&sequences[0]),
+            vec![first[1].clone(), second[0].clone()]
+        );
+
+        // The window of the sequences started in the first block still covers the third.
+        let (sequences, _) = block(&[], rng);
+        assert!(sequences.is_empty());
+
+        // It is closed by the fourth, so the sequences of A and D time out before A's second
+        // payment is seen, oldest first.
+        let (sequences, _) = block(&[(a, 2)], rng);
+        assert_eq!(sequences.len(), 2);
+        assert!(sequences
+            .iter()
+            .all(|sequence| sequence["outcome"] == "TimedOut"));
+        assert_eq!(transaction_ids(&sequences[0]), vec![first[0].clone()]);
+        assert_eq!(transaction_ids(&sequences[1]), vec![first[3].clone()]);
+
+        // Completed and timed out sequences are no longer tracked.
+        let sequences = monitor.sequences.lock();
+        let partials = &sequences[&(*subscription.id(), event.name.clone())];
+        assert!(partials.waiting.is_empty());
+        assert_eq!(partials.started, 3);
+    }
+}
@@ -0,0 +1,83 @@
+use super::{Monitor, Verdict};
+use crate::{
+    EventDetails, EventManifest, EventPayLoad, Subscription, TransitionEvent, TransitionValue,
+};
+use anyhow::Result;
+use snarkvm::ledger::block::{ConfirmedTransaction, Transactions, Transition};
+use snarkvm::ledger::store::ConsensusStorage;
+use snarkvm::prelude::Network;
+use tracing::info;
+
+impl<N: Network, C: ConsensusStorage<N>> Monitor<N, C> {
+    /// Find the transitions in a block that match the manifest.
+    pub(crate) fn transition_events(
+        &self,
+        subscription: &Subscription<N>,
+        event: &EventManifest<N>,
+        height: u32,
+        transactions: &Transactions<N>,
+    ) -> Result<Vec<EventPayLoad<N>>> {
+        let mut payloads = vec![];
+        for transaction in transactions.iter() {
+            let (transaction_id, status) = Self::confirmation(transaction)?;
+            let fee = Self::fee(transaction)?;
+            if !event.status.includes(status) || !event.matches_fee(fee.as_ref()) {
+                continue;
+            }
+            info!("Transaction {transaction:?} being searched");
+            for transition in Self::executed_transitions(transaction) {
+                let event_name = &event.name;
+                let transition_program = transition.program_id();
+                let transition_function = transition.function_name();
+                info!("Search event: {event_name}-Transition: {transition_program}/{transition_function}");
+                let Verdict::Accepted(decision_proof) =
+                    self.verdict(subscription, event, transition, true)
+                else {
+                    continue;
+                };
+                let inclusion_proof = if event.inclusion_proof {
+                    self.inclusion_proof(height, transaction, transition)?
+                } else {
+                    None
+                };
+                let details = TransitionEvent::new(
+                    *transition_program,
+                    *transition_function,
+                    transaction_id,
+                    *transition.id(),
+                    status,
+                    Some(TransitionValue::inputs(transition)),
+                    Some(TransitionValue::outputs(transition)),
+                );
+                payloads.push(
+                    EventPayLoad::new(
+                        event.name.clone(),
+                        event.description.clone(),
+                        height,
+                        fee,
+                        EventDetails::Transition(details),
+                    )
+                    .with_decision_proof(decision_proof)
+                    .with_inclusion_proof(inclusion_proof),
+                );
+            }
+        }
+        Ok(payloads)
+    }
+
+    /// Returns the transitions a transaction executed. A rejected execution is replaced on chain
+    /// by a fee transaction, so its transitions are taken from the rejected execution followed by
+    /// the fee transition.
+    pub(super) fn executed_transitions(
+        transaction: &ConfirmedTransaction<N>,
+    ) -> impl Iterator<Item = &Transition<N>> {
+        let rejected = match transaction {
+            ConfirmedTransaction::RejectedExecute(_, _, rejected, _) => rejected.execution(),
+            _ => None,
+        };
+        rejected
+            .into_iter()
+            .flat_map(|execution| execution.transitions())
+            .chain(transaction.transitions())
+    }
+}
@@ -0,0 +1,236 @@
+use super::cast::{cast, cast_ref};
+use super::Monitor;
+use crate::{EventDetails, EventPayLoad};
+use anyhow::{bail, Result};
+use indexmap::IndexMap;
+use rand::thread_rng;
+use snarkvm::circuit::{Aleo, AleoCanaryV0, AleoTestnetV0, AleoV0};
+use snarkvm::console::network::{CanaryV0, MainnetV0, TestnetV0};
+use snarkvm::ledger::block::{ConfirmedTransaction, Deployment, Transactions};
+use snarkvm::ledger::store::ConsensusStorage;
+use snarkvm::prelude::{Network, ProgramID};
+use snarkvm::synthesizer::Process;
+
+impl<N: Network, C: ConsensusStorage<N>> Monitor<N, C> {
+    /// Re-verify the transactions in the block each payload was found in, and replace payloads
+    /// whose transactions fail with verification failure events. Sequence steps and aggregate
+    /// samples can come from earlier blocks, so they are verified when they match instead.
+    pub(super) fn verify_payloads(
+        &self,
+        payloads: Vec<EventPayLoad<N>>,
+        height: u32,
+        transactions: &Transactions<N>,
+    ) -> Result<Vec<EventPayLoad<N>>> {
+        let mut checked = Vec::with_capacity(payloads.len());
+        'payloads: for payload in payloads {
+            if matches!(
+                payload.details(),
+                EventDetails::Sequence(_) | EventDetails::Aggregate(_)
+            ) {
+                checked.push(payload);
+                continue;
+            }
+            for transaction_id in payload.details().transactions() {
+                let mut transaction = None;
+                for confirmed in transactions.iter() {
+                    if Self::confirmation(confirmed)?.0 == transaction_id {
+                        transaction = Some(confirmed);
+                        break;
+                    }
+                }
+                let error = match transaction {
+                    Some(transaction) => self
+                        .verification_failure(height, transaction)?
+                        .map(|(_, error)| error),
+                    None => Some(format!(
+                        "Transaction {transaction_id} is not in block {height}"
+                    )),
+                };
+                if let Some(error) = error {
+                    checked.push(payload.into_verification_failure(transaction_id, error));
+                    continue 'payloads;
+                }
+            }
+            checked.push(payload);
+        }
+        Ok(checked)
+    }
+
+    /// Returns the ID a transaction was broadcast with and why it failed verification, or
+    /// `None` if it passed. Results are cached by transaction ID for the rest of the block.
+    pub(super) fn verification_failure(
+        &self,
+        height: u32,
+        transaction: &ConfirmedTransaction<N>,
+    ) -> Result<Option<(N::TransactionID, String)>> {
+        let (transaction_id, _) = Self::confirmation(transaction)?;
+        {
+            let mut verified = self.verified.lock();
+            if verified.0 != height {
+                *verified = (height, IndexMap::new());
+            }
+            if let Some(error) = verified.1.get(&transaction_id) {
+                return Ok(error.clone().map(|error| (transaction_id, error)));
+            }
+        }
+        let error = self
+            .verify_transaction(transaction)
+            .err()
+            .map(|e| e.to_string());
+        self.verified.lock().1.insert(transaction_id, error.clone());
+        Ok(error.map(|error| (transaction_id, error)))
+    }
+
+    /// Verify the execution or deployment a transaction holds, including one that was
+    /// rejected, and its fee.
+    fn verify_transaction(&self, transaction: &ConfirmedTransaction<N>) -> Result<()> {
+        let rejected = match transaction {
+            ConfirmedTransaction::RejectedExecute(_, _, rejected, _)
+            | ConfirmedTransaction::RejectedDeploy(_, _, rejected, _) => Some(rejected),
+            _ => None,
+        };
+        let execution = rejected
+            .and_then(|rejected| rejected.execution())
+            .or(transaction.execution());
+        let deployment = rejected
+            .and_then(|rejected| rejected.deployment())
+            .or(transaction.deployment());
+
+        // The ledger's process is only locked for the checks it runs, so it is free while a
+        // deployment is verified.
+        let process = self.ledger.vm().process();
+        let id = if let Some(execution) = execution {
+            process.read().verify_execution(execution)?;
+            execution.to_execution_id()?
+        } else if let Some(deployment) = deployment {
+            self.verify_deployment(deployment)?;
+            deployment.to_deployment_id()?
+        } else {
+            bail!(
+                "Transaction {} holds no execution or deployment",
+                transaction.id()
+            )
+        };
+        if let Some(fee) = transaction.fee_transition() {
+            process.read().verify_fee(&fee, id)?;
+        }
+        Ok(())
+    }
+
+    /// Verify a deployment in a scratch process. The ledger's process already holds the
+    /// deployed program, so it cannot verify the deployment again.
+    fn verify_deployment(&self, deployment: &Deployment<N>) -> Result<()> {
+        let mut verifier = self.verifier.lock();
+        if verifier.is_none() {
+            *verifier = Some(Process::load()?);
+        }
+        let verifier = verifier.as_mut().expect("the verifier was just loaded");
+        for import in deployment.program().imports().keys() {
+            self.load_program(verifier, import)?;
+        }
+
+        macro_rules! verify {
+            ($network:ty, $aleo:ty) => {
+                verify_deployment::<$network, $aleo>(cast_ref(verifier)?, cast_ref(deployment)?)
+            };
+        }
+        match N::ID {
+            MainnetV0::ID => verify!(MainnetV0, AleoV0),
+            TestnetV0::ID => verify!(TestnetV0, AleoTestnetV0),
+            CanaryV0::ID => verify!(CanaryV0, AleoCanaryV0),
+            id => bail!("Unknown network ID ({id})"),
+        }
+    }
+
+    /// Add a deployed program and the programs it imports to the process.
+    fn load_program(&self, process: &mut Process<N>, program_id: &ProgramID<N>) -> Result<()> {
+        if process.contains_program(program_id) {
+            return Ok(());
+        }
+        let program = self.ledger.get_program(*program_id)?;
+        for import in program.imports().keys() {
+            self.load_program(process, import)?;
+        }
+        process.add_program(&program)
+    }
+}
+
+fn verify_deployment<N: Network, A: Aleo<Network = N>>(
+    process: &Process<N>,
+    deployment: &Deployment<N>,
+) -> Result<()> {
+    process.verify_deployment::<A, _>(deployment, &mut thread_rng())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_helpers::{sample_ledger, CurrentNetwork};
+    use super::*;
+    use crate::{EventManifest, EventManifests, Subscription};
+    use snarkvm::ledger::block::{Execution, Transaction};
+    use snarkvm::prelude::{PrivateKey, TestRng};
+
+    #[test]
+    fn test_verify_payloads() {
+        let rng = &mut TestRng::default();
+        let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let ledger = sample_ledger(&private_key, rng);
+        let manifest: EventManifest<CurrentNetwork> = serde_json::from_value(serde_json::json!({
+            "name": "genesisTransfers",
+            "description": "Genesis transfers",
+            "program": "credits.aleo",
+            "function": "transfer_public_to_private",
+            "actions": ["Notify"],
+        }))
+        .unwrap();
+        let subscription = Subscription::new(EventManifests::new(vec![manifest.clone()])).unwrap();
+
+        // The genesis transactions verify, so their events are reported as found.
+        let monitor = Monitor::new(ledger.clone());
+        let transactions = ledger.get_transactions(0).unwrap();
+        let payloads = monitor
+            .transition_events(&subscription, &manifest, 0, &transactions)
+            .unwrap();
+        assert!(!payloads.is_empty());
+        let checked = monitor
+            .verify_payloads(payloads.clone(), 0, &transactions)
+            .unwrap();
+        assert_eq!(checked, payloads);
+
+        // Swap in the proof of another transaction.
+        let mut genesis = transactions.iter();
+        let execution = genesis.next().unwrap().execution().unwrap();
+        let proof = genesis
+            .next()
+            .unwrap()
+            .execution()
+            .unwrap()
+            .proof()
+            .cloned();
+        let tampered = Execution::from(
+            execution.transitions().cloned(),
+            execution.global_state_root(),
+            proof,
+        )
+        .unwrap();
+        let tampered = ConfirmedTransaction::accepted_execute(
+            0,
+            Transaction::from_execution(tampered, None).unwrap(),
+            vec![],
+        )
+        .unwrap();
+        assert!(monitor.verify_transaction(&tampered).is_err());
+
+        let monitor = Monitor::new(ledger);
+        let transactions = Transactions::from_iter([tampered]);
+        let payloads = monitor
+            .transition_events(&subscription, &manifest, 0, &transactions)
+            .unwrap();
+        assert_eq!(payloads.len(), 1);
+        let checked = monitor.verify_payloads(payloads, 0, &transactions).unwrap();
+        assert!(matches!(
+            checked[0].details(),
+            EventDetails::VerificationFailed(_)
+        ));
+    }
+}
@@ -0,0 +1,103 @@
+use super::Monitor;
+use crate::{
+    ChainAction, DeliveryState, DeliveryStatus, EventManifest, EventPayLoad, SubscriptionID,
+    INITIAL_BACKOFF,
+};
+use indexmap::IndexMap;
+use snarkvm::ledger::store::ConsensusStorage;
+use snarkvm::prelude::Network;
+use tracing::warn;
+
+/// Number of events per subscription whose webhook deliveries are kept. Older ones are
+/// forgotten, whether or not they finished.
+const MAX_TRACKED_EVENTS: usize = 1024;
+
+impl<N: Network, C: ConsensusStorage<N>> Monitor<N, C> {
+    /// Post an event to every webhook of its manifest in the background. Each delivery is
+    /// recorded as pending until it succeeds or is given up on.
+    pub(super) fn deliver(
+        &self,
+        subscription_id: &SubscriptionID<N>,
+        event: &EventManifest<N>,
+        payload: &EventPayLoad<N>,
+    ) {
+        let webhooks = event
+            .actions
+            .iter()
+            .filter_map(|action| match action {
+                ChainAction::Webhook(webhook) => Some(webhook.clone()),
+                ChainAction::Notify => None,
+            })
+            .collect::<Vec<_>>();
+        if webhooks.is_empty() {
+            return;
+        }
+        let body = match serde_json::to_vec(payload) {
+            Ok(body) => body,
+            Err(error) => {
+                warn!(
+                    "Failed to encode event {} for its webhooks: {error}",
+                    payload.id()
+                );
+                return;
+            }
+        };
+        let event_id = payload.id();
+        {
+            let mut deliveries = self.deliveries.lock();
+            let events = deliveries.entry(*subscription_id).or_default();
+            events.insert(
+                event_id,
+                webhooks
+                    .iter()
+                    .map(|webhook| DeliveryStatus::new(webhook.url.clone()))
+                    .collect(),
+            );
+            while events.len() > MAX_TRACKED_EVENTS {
+                events.shift_remove_index(0);
+            }
+        }
+        for (index, webhook) in webhooks.into_iter().enumerate() {
+            let client = self.http.clone();
+            let deliveries = self.deliveries.clone();
+            let subscription_id = *subscription_id;
+            let body = body.clone();
+            tokio::spawn(async move {
+                let status = webhook.deliver(&client, body, INITIAL_BACKOFF).await;
+                if status.state() == DeliveryState::Failed {
+                    warn!(
+                        "Gave up delivering event {event_id} to {}: {}",
+                        webhook.url,
+                        status.error().unwrap_or_default()
+                    );
+                }
+                if let Some(pending) = deliveries
+                    .lock()
+                    .get_mut(&subscription_id)
+                    .and_then(|events| events.get_mut(&event_id))
+                    .and_then(|statuses| statuses.get_mut(index))
+                {
+                    *pending = status;
+                }
+            });
+        }
+    }
+
+    /// Get the webhook deliveries of a subscription's events, keyed by the `id` of their payloads.
+    /// Events without webhooks have no entry. Deliveries outlive the draining of their events,
+    /// so an entry may belong to an event returned by an earlier drain. Events whose deliveries
+    /// have all finished are reported once and then forgotten.
+    pub fn deliveries(&self, id: &SubscriptionID<N>) -> IndexMap<u64, Vec<DeliveryStatus>> {
+        let mut deliveries = self.deliveries.lock();
+        let Some(events) = deliveries.get_mut(id) else {
+            return IndexMap::new();
+        };
+        let reported = events.clone();
+        events.retain(|_, statuses| {
+            statuses
+                .iter()
+                .any(|status| status.state() == DeliveryState::Pending)
+        });
+        reported
+    }
+}
@@ -0,0 +1,29 @@
+use snarkvm::prelude::{Network, ToBytes};
+
+use serde::{Deserialize, Serialize};
+use std::io::{Result as IoResult, Write};
+
+/// A transaction the block aborted. The ledger keeps nothing but its ID.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(bound(serialize = "N: Serialize", deserialize = "N: for<'a> Deserialize<'a>"))]
+pub struct AbortedEvent<N: Network> {
+    // Transaction aborted.
+    transaction: N::TransactionID,
+}
+
+impl<N: Network> AbortedEvent<N> {
+    pub fn new(transaction: N::TransactionID) -> AbortedEvent<N> {
+        AbortedEvent { transaction }
+    }
+
+    /// Get the transaction aborted.
+    pub fn transaction(&self) -> &N::TransactionID {
+        &self.transaction
+    }
+}
+
+impl<N: Network> ToBytes for AbortedEvent<N> {
+    fn write_le<W: Write>(&self, writer: W) -> IoResult<()> {
+        self.transaction.write_le(writer)
+    }
+}
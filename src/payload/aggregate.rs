@@ -0,0 +1,55 @@
+use super::bytes::write_list;
+use crate::AggregateFunction;
+use snarkvm::prelude::{Network, ToBytes};
+
+use serde::{Deserialize, Serialize};
+use std::io::{Result as IoResult, Write};
+
+/// An aggregate over a window of blocks that crossed its threshold.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(bound(serialize = "N: Serialize", deserialize = "N: for<'a> Deserialize<'a>"))]
+pub struct AggregateEvent<N: Network> {
+    // How the samples were combined.
+    function: AggregateFunction,
+    // The aggregate value.
+    value: i128,
+    // Number of samples in the window.
+    samples: u64,
+    // First block height of the window.
+    window_start: u32,
+    // Transactions the samples were found in.
+    transactions: Vec<N::TransactionID>,
+}
+
+impl<N: Network> AggregateEvent<N> {
+    pub fn new(
+        function: AggregateFunction,
+        value: i128,
+        samples: u64,
+        window_start: u32,
+        transactions: Vec<N::TransactionID>,
+    ) -> AggregateEvent<N> {
+        AggregateEvent {
+            function,
+            value,
+            samples,
+            window_start,
+            transactions,
+        }
+    }
+
+    /// Get the transactions the samples were found in.
+    pub fn transactions(&self) -> &[N::TransactionID] {
+        &self.transactions
+    }
+}
+
+impl<N: Network> ToBytes for AggregateEvent<N> {
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        (self.function as u8).write_le(&mut writer)?;
+        self.value.write_le(&mut writer)?;
+        self.samples.write_le(&mut writer)?;
+        self.window_start.write_le(&mut writer)?;
+        write_list(&self.transactions, writer)
+    }
+}
@@ -0,0 +1,65 @@
+use super::bytes::write_list;
+use snarkvm::prelude::{Address, Network, ToBytes};
+
+use serde::{Deserialize, Serialize};
+use std::io::{Result as IoResult, Write};
+
+/// A block and its header metadata.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(bound(serialize = "N: Serialize", deserialize = "N: for<'a> Deserialize<'a>"))]
+pub struct BlockEvent<N: Network> {
+    // Block hash.
+    hash: N::BlockHash,
+    // Hash of the previous block.
+    previous_hash: N::BlockHash,
+    // Consensus round the block was created in.
+    round: u64,
+    // Block timestamp, in seconds since the Unix epoch.
+    timestamp: i64,
+    // Number of confirmed transactions.
+    transactions: u32,
+    // Coinbase target of the block.
+    coinbase_target: u64,
+    // Proof target of the block.
+    proof_target: u64,
+    // Provers of the puzzle solutions in the block.
+    provers: Vec<Address<N>>,
+}
+
+impl<N: Network> BlockEvent<N> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        hash: N::BlockHash,
+        previous_hash: N::BlockHash,
+        round: u64,
+        timestamp: i64,
+        transactions: u32,
+        coinbase_target: u64,
+        proof_target: u64,
+        provers: Vec<Address<N>>,
+    ) -> BlockEvent<N> {
+        BlockEvent {
+            hash,
+            previous_hash,
+            round,
+            timestamp,
+            transactions,
+            coinbase_target,
+            proof_target,
+            provers,
+        }
+    }
+}
+
+impl<N: Network> ToBytes for BlockEvent<N> {
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.hash.write_le(&mut writer)?;
+        self.previous_hash.write_le(&mut writer)?;
+        self.round.write_le(&mut writer)?;
+        self.timestamp.write_le(&mut writer)?;
+        self.transactions.write_le(&mut writer)?;
+        self.coinbase_target.write_le(&mut writer)?;
+        self.proof_target.write_le(&mut writer)?;
+        write_list(&self.provers, writer)
+    }
+}
@@ -0,0 +1,39 @@
+//! Helpers for the byte encoding event payloads are signed over. Values are written in
+//! snarkVM's little-endian byte encoding. Strings and lists are prefixed by their length as a
+//! `u32`, optional values by a `0` or `1` byte, and enum variants by their index as a `u8`.
+
+use snarkvm::prelude::ToBytes;
+
+use std::io::{Error, Result as IoResult, Write};
+
+/// Write a length as a `u32`.
+pub(crate) fn write_length<W: Write>(length: usize, writer: W) -> IoResult<()> {
+    u32::try_from(length)
+        .map_err(|_| Error::other("length does not fit in a u32"))?
+        .write_le(writer)
+}
+
+/// Write a string as its length followed by its UTF-8 bytes.
+pub(crate) fn write_string<W: Write>(string: &str, mut writer: W) -> IoResult<()> {
+    write_length(string.len(), &mut writer)?;
+    writer.write_all(string.as_bytes())
+}
+
+/// Write a `0` byte for `None`, or a `1` byte followed by the value.
+pub(crate) fn write_option<T: ToBytes, W: Write>(value: Option<&T>, mut writer: W) -> IoResult<()> {
+    match value {
+        None => 0u8.write_le(writer),
+        Some(value) => {
+            1u8.write_le(&mut writer)?;
+            value.write_le(writer)
+        }
+    }
+}
+
+/// Write a list as its length followed by its elements.
+pub(crate) fn write_list<T: ToBytes, W: Write>(values: &[T], mut writer: W) -> IoResult<()> {
+    write_length(values.len(), &mut writer)?;
+    values
+        .iter()
+        .try_for_each(|value| value.write_le(&mut writer))
+}
@@ -0,0 +1,89 @@
+use super::bytes::write_option;
+use crate::CommitteeChange;
+use snarkvm::prelude::{Address, Network, ToBytes};
+
+use serde::{Deserialize, Serialize};
+use std::io::{Result as IoResult, Write};
+
+/// A validator's committee membership.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ValidatorState {
+    // Bonded stake, in microcredits.
+    stake: u64,
+    // Whether the validator accepts delegators.
+    is_open: bool,
+    // Commission taken from delegators' rewards, in percent.
+    commission: u8,
+}
+
+impl ValidatorState {
+    pub fn new(stake: u64, is_open: bool, commission: u8) -> ValidatorState {
+        ValidatorState {
+            stake,
+            is_open,
+            commission,
+        }
+    }
+
+    /// Get the bonded stake.
+    pub fn stake(&self) -> u64 {
+        self.stake
+    }
+
+    /// Returns `true` if the validator accepts delegators.
+    pub fn is_open(&self) -> bool {
+        self.is_open
+    }
+
+    /// Get the commission.
+    pub fn commission(&self) -> u8 {
+        self.commission
+    }
+}
+
+/// A change to a validator's committee membership.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(bound(serialize = "N: Serialize", deserialize = "N: for<'a> Deserialize<'a>"))]
+pub struct CommitteeEvent<N: Network> {
+    // Validator that changed.
+    validator: Address<N>,
+    // What changed.
+    change: CommitteeChange,
+    // Membership in the previous block's committee.
+    before: Option<ValidatorState>,
+    // Membership in this block's committee.
+    after: Option<ValidatorState>,
+}
+
+impl<N: Network> CommitteeEvent<N> {
+    pub fn new(
+        validator: Address<N>,
+        change: CommitteeChange,
+        before: Option<ValidatorState>,
+        after: Option<ValidatorState>,
+    ) -> CommitteeEvent<N> {
+        CommitteeEvent {
+            validator,
+            change,
+            before,
+            after,
+        }
+    }
+}
+
+impl ToBytes for ValidatorState {
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.stake.write_le(&mut writer)?;
+        self.is_open.write_le(&mut writer)?;
+        self.commission.write_le(writer)
+    }
+}
+
+impl<N: Network> ToBytes for CommitteeEvent<N> {
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.validator.write_le(&mut writer)?;
+        (self.change as u8).write_le(&mut writer)?;
+        write_option(self.before.as_ref(), &mut writer)?;
+        write_option(self.after.as_ref(), writer)
+    }
+}
@@ -0,0 +1,45 @@
+use super::bytes::write_list;
+use crate::ConfirmationStatus;
+use snarkvm::prelude::{Network, ToBytes};
+
+use serde::{Deserialize, Serialize};
+use std::io::{Result as IoResult, Write};
+
+/// A transaction holding every transition a composite manifest asks for.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(bound(serialize = "N: Serialize", deserialize = "N: for<'a> Deserialize<'a>"))]
+pub struct CompositeEvent<N: Network> {
+    // Transaction the transitions were found in.
+    transaction: N::TransactionID,
+    // Whether the transaction was accepted or rejected.
+    status: ConfirmationStatus,
+    // Transitions matching each condition, in the order the conditions are listed.
+    transitions: Vec<N::TransitionID>,
+}
+
+impl<N: Network> CompositeEvent<N> {
+    pub fn new(
+        transaction: N::TransactionID,
+        status: ConfirmationStatus,
+        transitions: Vec<N::TransitionID>,
+    ) -> CompositeEvent<N> {
+        CompositeEvent {
+            transaction,
+            status,
+            transitions,
+        }
+    }
+
+    /// Get the transaction the event was found in.
+    pub fn transaction(&self) -> &N::TransactionID {
+        &self.transaction
+    }
+}
+
+impl<N: Network> ToBytes for CompositeEvent<N> {
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.transaction.write_le(&mut writer)?;
+        (self.status as u8).write_le(&mut writer)?;
+        write_list(&self.transitions, writer)
+    }
+}
@@ -0,0 +1,192 @@
+use super::bytes::write_list;
+use crate::{input_plaintext, output_plaintext};
+use snarkvm::ledger::block::Execution;
+use snarkvm::prelude::{Boolean, Identifier, Literal, Network, Plaintext, ProgramID, ToBytes};
+use snarkvm::synthesizer::program::Program;
+use snarkvm::synthesizer::snark::VerifyingKey;
+use snarkvm::synthesizer::Process;
+
+use anyhow::{anyhow, ensure, Result};
+use serde::{Deserialize, Serialize};
+use std::io::{Result as IoResult, Write};
+
+/// A proof that a decision function returned `true` for the inputs taken from a transition.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(bound(serialize = "N: Serialize", deserialize = "N: for<'a> Deserialize<'a>"))]
+pub struct DecisionProof<N: Network> {
+    // Decision program.
+    program: ProgramID<N>,
+    // Decision function.
+    function: Identifier<N>,
+    // Inputs the function was called with.
+    inputs: Vec<Plaintext<N>>,
+    // Proven execution of the function.
+    execution: Execution<N>,
+    // Verifying key the monitor proved against. Verifiers should compare it with a key they
+    // derive from the program themselves.
+    verifying_key: VerifyingKey<N>,
+}
+
+impl<N: Network> DecisionProof<N> {
+    pub fn new(
+        program: ProgramID<N>,
+        function: Identifier<N>,
+        inputs: Vec<Plaintext<N>>,
+        execution: Execution<N>,
+        verifying_key: VerifyingKey<N>,
+    ) -> DecisionProof<N> {
+        DecisionProof {
+            program,
+            function,
+            inputs,
+            execution,
+            verifying_key,
+        }
+    }
+
+    /// Get the inputs the function was called with.
+    pub fn inputs(&self) -> &[Plaintext<N>] {
+        &self.inputs
+    }
+
+    /// Get the verifying key the monitor proved against.
+    pub fn verifying_key(&self) -> &VerifyingKey<N> {
+        &self.verifying_key
+    }
+
+    /// Verify the execution against the decision program and a trusted verifying key for the
+    /// function, and check that the proven call took the reported inputs and returned `true`.
+    /// Nothing is read from the ledger, so this works offline.
+    pub fn verify(&self, program: &Program<N>, verifying_key: VerifyingKey<N>) -> Result<()> {
+        ensure!(
+            *program.id() == self.program,
+            "The proof is for {}, not {}",
+            self.program,
+            program.id()
+        );
+        let process = {
+            let mut process = Process::load()?;
+            process.add_program(program)?;
+            process.insert_verifying_key(&self.program, &self.function, verifying_key)?;
+            process
+        };
+        process.verify_execution(&self.execution)?;
+
+        // The decision function's own transition comes last.
+        let transition = self
+            .execution
+            .transitions()
+            .last()
+            .ok_or_else(|| anyhow!("The execution has no transitions"))?;
+        ensure!(
+            *transition.program_id() == self.program
+                && *transition.function_name() == self.function,
+            "The execution is not a call to {}/{}",
+            self.program,
+            self.function
+        );
+        let inputs = transition
+            .inputs()
+            .iter()
+            .map(input_plaintext)
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| anyhow!("The execution has inputs that are not public"))?;
+        ensure!(
+            inputs.into_iter().eq(self.inputs.iter()),
+            "The execution was called with different inputs"
+        );
+        let accepted = Plaintext::from(Literal::Boolean(Boolean::new(true)));
+        ensure!(
+            matches!(transition.outputs(), [output] if output_plaintext(output) == Some(&accepted)),
+            "The execution did not return true"
+        );
+        Ok(())
+    }
+}
+
+impl<N: Network> ToBytes for DecisionProof<N> {
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.program.write_le(&mut writer)?;
+        self.function.write_le(&mut writer)?;
+        write_list(&self.inputs, &mut writer)?;
+        self.execution.write_le(&mut writer)?;
+        self.verifying_key.write_le(writer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monitor::test_helpers::{sample_ledger, CurrentNetwork};
+    use crate::monitor::Monitor;
+    use crate::{EventManifests, Subscription};
+    use snarkvm::circuit::AleoV0;
+    use snarkvm::prelude::{PrivateKey, TestRng};
+    use std::str::FromStr;
+
+    const DECISION: &str = r"program decision_proof.aleo;
+
+function decide:
+    input r0 as u64.public;
+    gt r0 0u64 into r1;
+    output r1 as boolean.public;
+";
+
+    #[test]
+    fn test_verify_decision_proof() {
+        let rng = &mut TestRng::default();
+        let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let ledger = sample_ledger(&private_key, rng);
+
+        // Prove the decision on the amount of a genesis transfer.
+        let manifest = serde_json::from_value(serde_json::json!({
+            "name": "provenTransfers",
+            "description": "",
+            "program": "credits.aleo",
+            "function": "transfer_public_to_private",
+            "decision": {
+                "program": DECISION,
+                "function": "decide",
+                "arguments": ["input[1]"],
+                "prove": true,
+            },
+            "actions": ["Notify"],
+        }))
+        .unwrap();
+        let subscription = Subscription::new(EventManifests::new(vec![manifest])).unwrap();
+        let mut monitor = Monitor::new(ledger.clone());
+        monitor.add(subscription.clone()).unwrap();
+        let transactions = ledger.get_transactions(0).unwrap();
+        let payloads = monitor
+            .transition_events(&subscription, &subscription.events()[0], 0, &transactions)
+            .unwrap();
+        assert_eq!(payloads.len(), 4);
+        let proof = payloads[0].decision_proof().unwrap().clone();
+
+        // A verifier derives the verifying key from the program on its own.
+        let program = Program::<CurrentNetwork>::from_str(DECISION).unwrap();
+        let function = Identifier::from_str("decide").unwrap();
+        let verifying_key = {
+            let mut process = Process::load().unwrap();
+            process.add_program(&program).unwrap();
+            process
+                .synthesize_key::<AleoV0, _>(program.id(), &function, rng)
+                .unwrap();
+            process.get_verifying_key(*program.id(), function).unwrap()
+        };
+        proof.verify(&program, verifying_key.clone()).unwrap();
+
+        // The proof does not hold for other inputs than the ones proven with.
+        let mut tampered = proof.clone();
+        tampered.inputs = vec![Plaintext::from_str("1u64").unwrap()];
+        assert_ne!(tampered.inputs, proof.inputs);
+        assert!(tampered.verify(&program, verifying_key.clone()).is_err());
+
+        // Nor for another program.
+        let other = Program::<CurrentNetwork>::from_str(
+            &DECISION.replace("decision_proof.aleo", "decision_other.aleo"),
+        )
+        .unwrap();
+        assert!(proof.verify(&other, verifying_key).is_err());
+    }
+}
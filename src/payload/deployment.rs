@@ -0,0 +1,60 @@
+use super::bytes::write_list;
+use crate::ConfirmationStatus;
+use snarkvm::prelude::{Identifier, Network, ProgramID, ToBytes};
+
+use serde::{Deserialize, Serialize};
+use std::io::{Result as IoResult, Write};
+
+/// A program deployed in a block.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(bound(serialize = "N: Serialize", deserialize = "N: for<'a> Deserialize<'a>"))]
+pub struct DeploymentEvent<N: Network> {
+    // Program deployed.
+    program: ProgramID<N>,
+    // Transaction that deployed the program.
+    transaction: N::TransactionID,
+    // Whether the deployment was accepted or rejected.
+    status: ConfirmationStatus,
+    // Programs imported.
+    imports: Vec<ProgramID<N>>,
+    // Functions declared.
+    functions: Vec<Identifier<N>>,
+    // Mappings declared.
+    mappings: Vec<Identifier<N>>,
+}
+
+impl<N: Network> DeploymentEvent<N> {
+    pub fn new(
+        program: ProgramID<N>,
+        transaction: N::TransactionID,
+        status: ConfirmationStatus,
+        imports: Vec<ProgramID<N>>,
+        functions: Vec<Identifier<N>>,
+        mappings: Vec<Identifier<N>>,
+    ) -> DeploymentEvent<N> {
+        DeploymentEvent {
+            program,
+            transaction,
+            status,
+            imports,
+            functions,
+            mappings,
+        }
+    }
+
+    /// Get the transaction the event was found in.
+    pub fn transaction(&self) -> &N::TransactionID {
+        &self.transaction
+    }
+}
+
+impl<N: Network> ToBytes for DeploymentEvent<N> {
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.program.write_le(&mut writer)?;
+        self.transaction.write_le(&mut writer)?;
+        (self.status as u8).write_le(&mut writer)?;
+        write_list(&self.imports, &mut writer)?;
+        write_list(&self.functions, &mut writer)?;
+        write_list(&self.mappings, writer)
+    }
+}
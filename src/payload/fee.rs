@@ -0,0 +1,53 @@
+use snarkvm::prelude::ToBytes;
+
+use serde::{Deserialize, Serialize};
+use std::io::{Result as IoResult, Write};
+
+/// The fee a transaction paid, in microcredits.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FeeDetails {
+    // Base fee for storage and execution.
+    base_amount: u64,
+    // Priority fee paid on top of the base fee.
+    priority_amount: u64,
+    // Whether the fee was paid with `fee_public` rather than `fee_private`.
+    public: bool,
+}
+
+impl FeeDetails {
+    pub fn new(base_amount: u64, priority_amount: u64, public: bool) -> FeeDetails {
+        FeeDetails {
+            base_amount,
+            priority_amount,
+            public,
+        }
+    }
+
+    /// Get the base fee.
+    pub fn base_amount(&self) -> u64 {
+        self.base_amount
+    }
+
+    /// Get the priority fee.
+    pub fn priority_amount(&self) -> u64 {
+        self.priority_amount
+    }
+
+    /// Get the total fee.
+    pub fn total_amount(&self) -> u64 {
+        self.base_amount.saturating_add(self.priority_amount)
+    }
+
+    /// Returns `true` if the fee was paid publicly.
+    pub fn is_public(&self) -> bool {
+        self.public
+    }
+}
+
+impl ToBytes for FeeDetails {
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.base_amount.write_le(&mut writer)?;
+        self.priority_amount.write_le(&mut writer)?;
+        self.public.write_le(writer)
+    }
+}
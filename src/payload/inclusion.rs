@@ -0,0 +1,194 @@
+use snarkvm::ledger::block::{
+    Block, ConfirmedTransaction, HeaderLeaf, HeaderPath, TransactionLeaf, TransactionPath,
+    TransactionsPath,
+};
+use snarkvm::prelude::{to_bits_le, Field, Network, ToBits, ToBytes};
+
+use anyhow::{ensure, Result};
+use serde::{Deserialize, Serialize};
+use std::io::{Result as IoResult, Write};
+
+/// Evidence that a transition is in a block: Merkle paths from the transition ID to its
+/// transaction ID, from the transaction ID to the block's transactions root, and from the
+/// transactions root to the header root, which is hashed with the previous block hash into the
+/// block hash.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(bound(serialize = "N: Serialize", deserialize = "N: for<'a> Deserialize<'a>"))]
+pub struct InclusionProof<N: Network> {
+    // Hash of the block the transition is in.
+    block_hash: N::BlockHash,
+    // Hash of the previous block.
+    previous_hash: N::BlockHash,
+    // Root of the block header tree.
+    header_root: Field<N>,
+    // Path from the transactions root to the header root.
+    header_path: HeaderPath<N>,
+    // Root of the block's transactions tree.
+    transactions_root: Field<N>,
+    // Position of the transaction in the block.
+    transaction_index: u32,
+    // Transaction as stored in the block. A rejected transaction is stored under its fee
+    // transaction's ID.
+    transaction: N::TransactionID,
+    // Path from the transaction ID to the transactions root.
+    transactions_path: TransactionsPath<N>,
+    // The transition's leaf in the transaction tree.
+    transaction_leaf: TransactionLeaf<N>,
+    // Path from the transition leaf to the transaction ID.
+    transaction_path: TransactionPath<N>,
+}
+
+impl<N: Network> InclusionProof<N> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        block_hash: N::BlockHash,
+        previous_hash: N::BlockHash,
+        header_root: Field<N>,
+        header_path: HeaderPath<N>,
+        transactions_root: Field<N>,
+        transaction_index: u32,
+        transaction: N::TransactionID,
+        transactions_path: TransactionsPath<N>,
+        transaction_leaf: TransactionLeaf<N>,
+        transaction_path: TransactionPath<N>,
+    ) -> InclusionProof<N> {
+        InclusionProof {
+            block_hash,
+            previous_hash,
+            header_root,
+            header_path,
+            transactions_root,
+            transaction_index,
+            transaction,
+            transactions_path,
+            transaction_leaf,
+            transaction_path,
+        }
+    }
+
+    /// Build the proof for a transition stored in a transaction of the block.
+    pub fn prove(
+        block: &Block<N>,
+        transaction: &ConfirmedTransaction<N>,
+        transition: &N::TransitionID,
+    ) -> Result<InclusionProof<N>> {
+        let header = block.header();
+        // The transactions root is the second leaf of the header tree.
+        let header_leaf = HeaderLeaf::<N>::new(1, header.transactions_root());
+        let transaction_leaf = transaction.to_leaf(transition)?;
+        Ok(InclusionProof::new(
+            block.hash(),
+            block.previous_hash(),
+            header.to_root()?,
+            header.to_path(&header_leaf)?,
+            header.transactions_root(),
+            transaction.index(),
+            transaction.id(),
+            block.transactions().to_path(transaction.id())?,
+            transaction_leaf,
+            transaction.to_path(&transaction_leaf)?,
+        ))
+    }
+
+    /// Get the hash of the block the transition is in.
+    pub fn block_hash(&self) -> &N::BlockHash {
+        &self.block_hash
+    }
+
+    /// Check that the transition is in the block with the trusted hash, at the transaction
+    /// position the proof states.
+    pub fn verify(&self, transition: &N::TransitionID, block_hash: &N::BlockHash) -> Result<()> {
+        ensure!(
+            self.block_hash == *block_hash,
+            "The proof is for block {}, not {block_hash}",
+            self.block_hash
+        );
+        ensure!(
+            self.transaction_leaf.id() == **transition,
+            "The proof is for another transition"
+        );
+        ensure!(
+            N::verify_merkle_path_bhp(
+                &self.transaction_path,
+                &self.transaction,
+                &self.transaction_leaf.to_bits_le()
+            ),
+            "The transition is not in transaction {}",
+            self.transaction
+        );
+        ensure!(
+            self.transactions_path.leaf_index() == self.transaction_index as u64,
+            "The transaction is not at position {}",
+            self.transaction_index
+        );
+        ensure!(
+            N::verify_merkle_path_bhp(
+                &self.transactions_path,
+                &self.transactions_root,
+                &self.transaction.to_bits_le()
+            ),
+            "Transaction {} is not in the block's transactions",
+            self.transaction
+        );
+        // The transactions root is the second leaf of the header tree.
+        let header_leaf = HeaderLeaf::<N>::new(1, self.transactions_root);
+        ensure!(
+            N::verify_merkle_path_bhp(
+                &self.header_path,
+                &self.header_root,
+                &header_leaf.to_bits_le()
+            ),
+            "The transactions root is not in the block header"
+        );
+        let expected = N::hash_bhp1024(&to_bits_le![self.previous_hash, self.header_root])?;
+        ensure!(
+            *self.block_hash == expected,
+            "The block header does not hash to block {}",
+            self.block_hash
+        );
+        Ok(())
+    }
+}
+
+impl<N: Network> ToBytes for InclusionProof<N> {
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.block_hash.write_le(&mut writer)?;
+        self.previous_hash.write_le(&mut writer)?;
+        self.header_root.write_le(&mut writer)?;
+        self.header_path.write_le(&mut writer)?;
+        self.transactions_root.write_le(&mut writer)?;
+        self.transaction_index.write_le(&mut writer)?;
+        self.transaction.write_le(&mut writer)?;
+        self.transactions_path.write_le(&mut writer)?;
+        self.transaction_leaf.write_le(&mut writer)?;
+        self.transaction_path.write_le(writer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::prelude::{FromBytes, MainnetV0};
+
+    type CurrentNetwork = MainnetV0;
+
+    #[test]
+    fn test_inclusion_proof() {
+        let block = Block::<CurrentNetwork>::read_le(CurrentNetwork::genesis_bytes()).unwrap();
+        let transaction = block.transactions().iter().last().unwrap();
+        let transition = transaction.transitions().next().unwrap().id();
+
+        let proof = InclusionProof::prove(&block, transaction, transition).unwrap();
+        assert!(proof.verify(transition, &block.hash()).is_ok());
+
+        // The proof does not hold for another block or another transition.
+        assert!(proof.verify(transition, &block.previous_hash()).is_err());
+        // The genesis block has several transactions, so the first is not the one proven.
+        assert!(block.transactions().len() > 1);
+        let other = block.transactions().iter().next().unwrap();
+        assert_ne!(other.id(), transaction.id());
+        let other = other.transitions().next().unwrap().id();
+        assert_ne!(other, transition);
+        assert!(proof.verify(other, &block.hash()).is_err());
+    }
+}
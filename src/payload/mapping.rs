@@ -0,0 +1,65 @@
+use super::bytes::write_option;
+use crate::MappingOperation;
+use snarkvm::prelude::{Identifier, Network, Plaintext, ProgramID, ToBytes};
+
+use serde::{Deserialize, Serialize};
+use std::io::{Result as IoResult, Write};
+
+/// A mapping insert, update or remove performed in finalize.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(bound(serialize = "N: Serialize", deserialize = "N: for<'a> Deserialize<'a>"))]
+pub struct MappingEvent<N: Network> {
+    // Program owning the mapping.
+    program: ProgramID<N>,
+    // Mapping updated.
+    mapping: Identifier<N>,
+    // Kind of update.
+    operation: MappingOperation,
+    // Transaction whose finalize performed the update.
+    transaction: N::TransactionID,
+    // Key updated, if it could be recovered from its key ID.
+    key: Option<Plaintext<N>>,
+    // Value before the update, if the monitor has seen it.
+    old_value: Option<Plaintext<N>>,
+    // Value after the update, if it is still the value stored in the ledger.
+    new_value: Option<Plaintext<N>>,
+}
+
+impl<N: Network> MappingEvent<N> {
+    pub fn new(
+        program: ProgramID<N>,
+        mapping: Identifier<N>,
+        operation: MappingOperation,
+        transaction: N::TransactionID,
+        key: Option<Plaintext<N>>,
+        old_value: Option<Plaintext<N>>,
+        new_value: Option<Plaintext<N>>,
+    ) -> MappingEvent<N> {
+        MappingEvent {
+            program,
+            mapping,
+            operation,
+            transaction,
+            key,
+            old_value,
+            new_value,
+        }
+    }
+
+    /// Get the transaction the event was found in.
+    pub fn transaction(&self) -> &N::TransactionID {
+        &self.transaction
+    }
+}
+
+impl<N: Network> ToBytes for MappingEvent<N> {
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.program.write_le(&mut writer)?;
+        self.mapping.write_le(&mut writer)?;
+        (self.operation as u8).write_le(&mut writer)?;
+        self.transaction.write_le(&mut writer)?;
+        write_option(self.key.as_ref(), &mut writer)?;
+        write_option(self.old_value.as_ref(), &mut writer)?;
+        write_option(self.new_value.as_ref(), writer)
+    }
+}
@@ -0,0 +1,348 @@
+use bytes::{write_option, write_string};
+use snarkvm::prelude::{Address, CryptoRng, Network, PrivateKey, Rng, Signature, ToBytes};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::io::{Result as IoResult, Write};
+
+pub mod aborted;
+pub use aborted::*;
+
+pub mod aggregate;
+pub use aggregate::*;
+
+pub mod block;
+pub use block::*;
+
+mod bytes;
+
+pub mod committee;
+pub use committee::*;
+
+pub mod composite;
+pub use composite::*;
+
+pub mod decision;
+pub use decision::*;
+
+pub mod deployment;
+pub use deployment::*;
+
+pub mod fee;
+pub use fee::*;
+
+pub mod inclusion;
+pub use inclusion::*;
+
+pub mod mapping;
+pub use mapping::*;
+
+pub mod record;
+pub use record::*;
+
+pub mod sequence;
+pub use sequence::*;
+
+pub mod spent;
+pub use spent::*;
+
+pub mod transition;
+pub use transition::*;
+
+pub mod verification;
+pub use verification::*;
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(bound(serialize = "N: Serialize", deserialize = "N: for<'a> Deserialize<'a>"))]
+pub struct EventPayLoad<N: Network> {
+    // Number the monitor gave the event. It is unique among the events the monitor reported
+    // since it started, and restarts from zero with the monitor.
+    id: u64,
+    // The event type.
+    event_type: String,
+    // User specified context.
+    context: String,
+    // Block height the event was found at.
+    block_height: u32,
+    // Fee paid by the transaction the event was found in.
+    fee: Option<FeeDetails>,
+    // What happened.
+    details: EventDetails<N>,
+    // Proof that the manifest's decision function reported the event.
+    decision_proof: Option<DecisionProof<N>>,
+    // Proof that the transition the event was found in is in the block.
+    inclusion_proof: Option<InclusionProof<N>>,
+    // Monitor's signature over the rest of the payload.
+    signature: Option<Signature<N>>,
+}
+
+impl<N: Network> EventPayLoad<N> {
+    pub fn new(
+        event_type: String,
+        context: String,
+        block_height: u32,
+        fee: Option<FeeDetails>,
+        details: EventDetails<N>,
+    ) -> EventPayLoad<N> {
+        EventPayLoad {
+            id: 0,
+            event_type,
+            context,
+            block_height,
+            fee,
+            details,
+            decision_proof: None,
+            inclusion_proof: None,
+            signature: None,
+        }
+    }
+
+    /// Number the event.
+    pub fn with_id(mut self, id: u64) -> Self {
+        self.id = id;
+        self
+    }
+
+    /// Get the number the monitor gave the event.
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Attach a proof of the decision that reported the event.
+    pub fn with_decision_proof(mut self, decision_proof: Option<DecisionProof<N>>) -> Self {
+        self.decision_proof = decision_proof;
+        self
+    }
+
+    /// Get the fee paid by the transaction the event was found in.
+    pub fn fee(&self) -> Option<&FeeDetails> {
+        self.fee.as_ref()
+    }
+
+    /// Get the event details.
+    pub fn details(&self) -> &EventDetails<N> {
+        &self.details
+    }
+
+    /// Attach a proof that the transition the event was found in is in the block.
+    pub fn with_inclusion_proof(mut self, inclusion_proof: Option<InclusionProof<N>>) -> Self {
+        self.inclusion_proof = inclusion_proof;
+        self
+    }
+
+    /// Get the proof of the decision that reported the event.
+    pub fn decision_proof(&self) -> Option<&DecisionProof<N>> {
+        self.decision_proof.as_ref()
+    }
+
+    /// Get the proof that the transition the event was found in is in the block.
+    pub fn inclusion_proof(&self) -> Option<&InclusionProof<N>> {
+        self.inclusion_proof.as_ref()
+    }
+
+    /// Replace the event with a report that its transaction failed verification. Proofs
+    /// attached to the event are dropped.
+    pub fn into_verification_failure(self, transaction: N::TransactionID, error: String) -> Self {
+        EventPayLoad::new(
+            self.event_type,
+            self.context,
+            self.block_height,
+            self.fee,
+            EventDetails::VerificationFailed(VerificationFailedEvent::new(
+                transaction,
+                error,
+                self.details,
+            )),
+        )
+    }
+
+    /// Get the monitor's signature.
+    pub fn signature(&self) -> Option<&Signature<N>> {
+        self.signature.as_ref()
+    }
+
+    /// Returns the bytes the monitor signs: every field but the signature, in the order they
+    /// are declared, in snarkVM's little-endian byte encoding. Strings and lists are prefixed
+    /// by their length as a `u32`, optional values by a `0` or `1` byte, enum variants by their
+    /// index as a `u8`, and transition inputs and outputs are sorted by index. Verifiers
+    /// re-encode the payload they received, so relays may reformat its JSON freely.
+    pub fn signing_bytes(&self) -> Result<Vec<u8>> {
+        let mut bytes = vec![];
+        self.id.write_le(&mut bytes)?;
+        write_string(&self.event_type, &mut bytes)?;
+        write_string(&self.context, &mut bytes)?;
+        self.block_height.write_le(&mut bytes)?;
+        write_option(self.fee.as_ref(), &mut bytes)?;
+        self.details.write_le(&mut bytes)?;
+        write_option(self.decision_proof.as_ref(), &mut bytes)?;
+        write_option(self.inclusion_proof.as_ref(), &mut bytes)?;
+        Ok(bytes)
+    }
+
+    /// Sign the payload with the monitor's private key.
+    pub fn sign<R: Rng + CryptoRng>(
+        &mut self,
+        private_key: &PrivateKey<N>,
+        rng: &mut R,
+    ) -> Result<()> {
+        self.signature = Some(Signature::sign_bytes(
+            private_key,
+            &self.signing_bytes()?,
+            rng,
+        )?);
+        Ok(())
+    }
+
+    /// Returns `true` if the payload is signed by the address.
+    pub fn verify(&self, address: &Address<N>) -> bool {
+        match (&self.signature, self.signing_bytes()) {
+            (Some(signature), Ok(message)) => signature.verify_bytes(address, &message),
+            _ => false,
+        }
+    }
+}
+
+/// The on-chain activity an event reports.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(bound(serialize = "N: Serialize", deserialize = "N: for<'a> Deserialize<'a>"))]
+pub enum EventDetails<N: Network> {
+    Transition(TransitionEvent<N>),
+    Mapping(MappingEvent<N>),
+    Deployment(DeploymentEvent<N>),
+    Record(RecordEvent<N>),
+    RecordSpent(RecordSpentEvent<N>),
+    Composite(CompositeEvent<N>),
+    Sequence(SequenceEvent<N>),
+    Aggregate(AggregateEvent<N>),
+    Block(BlockEvent<N>),
+    Committee(CommitteeEvent<N>),
+    VerificationFailed(VerificationFailedEvent<N>),
+    Aborted(AbortedEvent<N>),
+}
+
+impl<N: Network> EventDetails<N> {
+    /// Returns the transactions the event was found in, by the ID they were broadcast with.
+    pub fn transactions(&self) -> Vec<N::TransactionID> {
+        match self {
+            Self::Transition(event) => vec![*event.transaction()],
+            Self::Mapping(event) => vec![*event.transaction()],
+            Self::Deployment(event) => vec![*event.transaction()],
+            Self::Record(event) => vec![*event.transaction()],
+            Self::RecordSpent(event) => vec![*event.transaction()],
+            Self::Composite(event) => vec![*event.transaction()],
+            Self::Sequence(event) => event
+                .steps()
+                .iter()
+                .map(|step| *step.transaction())
+                .collect(),
+            Self::Aggregate(event) => event.transactions().to_vec(),
+            // Aborted transactions are not in the block, so there is nothing to verify.
+            Self::Block(_)
+            | Self::Committee(_)
+            | Self::VerificationFailed(_)
+            | Self::Aborted(_) => vec![],
+        }
+    }
+}
+
+impl<N: Network> ToBytes for EventDetails<N> {
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        match self {
+            Self::Transition(event) => {
+                0u8.write_le(&mut writer)?;
+                event.write_le(writer)
+            }
+            Self::Mapping(event) => {
+                1u8.write_le(&mut writer)?;
+                event.write_le(writer)
+            }
+            Self::Deployment(event) => {
+                2u8.write_le(&mut writer)?;
+                event.write_le(writer)
+            }
+            Self::Record(event) => {
+                3u8.write_le(&mut writer)?;
+                event.write_le(writer)
+            }
+            Self::RecordSpent(event) => {
+                4u8.write_le(&mut writer)?;
+                event.write_le(writer)
+            }
+            Self::Composite(event) => {
+                5u8.write_le(&mut writer)?;
+                event.write_le(writer)
+            }
+            Self::Sequence(event) => {
+                6u8.write_le(&mut writer)?;
+                event.write_le(writer)
+            }
+            Self::Aggregate(event) => {
+                7u8.write_le(&mut writer)?;
+                event.write_le(writer)
+            }
+            Self::Block(event) => {
+                8u8.write_le(&mut writer)?;
+                event.write_le(writer)
+            }
+            Self::Committee(event) => {
+                9u8.write_le(&mut writer)?;
+                event.write_le(writer)
+            }
+            Self::VerificationFailed(event) => {
+                10u8.write_le(&mut writer)?;
+                event.write_le(writer)
+            }
+            Self::Aborted(event) => {
+                11u8.write_le(&mut writer)?;
+                event.write_le(writer)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AggregateFunction;
+    use snarkvm::prelude::{MainnetV0, TestRng};
+
+    type CurrentNetwork = MainnetV0;
+
+    #[test]
+    fn test_signed_payload() {
+        let rng = &mut TestRng::default();
+        let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let address = Address::try_from(&private_key).unwrap();
+
+        let mut payload = EventPayLoad::<CurrentNetwork>::new(
+            "largeTransfers".to_string(),
+            "Transfers above the limit".to_string(),
+            7,
+            None,
+            EventDetails::Aggregate(AggregateEvent::new(
+                AggregateFunction::Sum,
+                500,
+                2,
+                5,
+                vec![],
+            )),
+        );
+        assert!(!payload.verify(&address));
+
+        payload.sign(&private_key, rng).unwrap();
+        assert!(payload.verify(&address));
+
+        // A payload that went through a relay as JSON still verifies.
+        let relayed: EventPayLoad<CurrentNetwork> =
+            serde_json::from_str(&serde_json::to_string_pretty(&payload).unwrap()).unwrap();
+        assert!(relayed.verify(&address));
+
+        let other = Address::try_from(&PrivateKey::<CurrentNetwork>::new(rng).unwrap()).unwrap();
+        assert!(!payload.verify(&other));
+        let tampered = EventPayLoad {
+            block_height: 8,
+            ..payload
+        };
+        assert!(!tampered.verify(&address));
+    }
+}
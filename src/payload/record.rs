@@ -0,0 +1,70 @@
+use crate::ConfirmationStatus;
+use snarkvm::prelude::{Field, Identifier, Network, Plaintext, ProgramID, Record, ToBytes};
+
+use serde::{Deserialize, Serialize};
+use std::io::{Result as IoResult, Write};
+
+/// A record output to the subscription's view key.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(bound(serialize = "N: Serialize", deserialize = "N: for<'a> Deserialize<'a>"))]
+pub struct RecordEvent<N: Network> {
+    // Program that created the record.
+    program: ProgramID<N>,
+    // Function that created the record.
+    function_id: Identifier<N>,
+    // Transaction the record was created in.
+    transaction: N::TransactionID,
+    // Transition the record was created in.
+    transition: N::TransitionID,
+    // Whether the transaction was accepted or rejected.
+    status: ConfirmationStatus,
+    // Output index of the record.
+    index: u32,
+    // Record commitment.
+    commitment: Field<N>,
+    // Decrypted record with its owner, fields and nonce.
+    record: Record<N, Plaintext<N>>,
+}
+
+impl<N: Network> RecordEvent<N> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        program: ProgramID<N>,
+        function_id: Identifier<N>,
+        transaction: N::TransactionID,
+        transition: N::TransitionID,
+        status: ConfirmationStatus,
+        index: u32,
+        commitment: Field<N>,
+        record: Record<N, Plaintext<N>>,
+    ) -> RecordEvent<N> {
+        RecordEvent {
+            program,
+            function_id,
+            transaction,
+            transition,
+            status,
+            index,
+            commitment,
+            record,
+        }
+    }
+
+    /// Get the transaction the event was found in.
+    pub fn transaction(&self) -> &N::TransactionID {
+        &self.transaction
+    }
+}
+
+impl<N: Network> ToBytes for RecordEvent<N> {
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.program.write_le(&mut writer)?;
+        self.function_id.write_le(&mut writer)?;
+        self.transaction.write_le(&mut writer)?;
+        self.transition.write_le(&mut writer)?;
+        (self.status as u8).write_le(&mut writer)?;
+        self.index.write_le(&mut writer)?;
+        self.commitment.write_le(&mut writer)?;
+        self.record.write_le(writer)
+    }
+}
@@ -0,0 +1,84 @@
+use super::bytes::write_list;
+use snarkvm::prelude::{Network, ToBytes};
+
+use serde::{Deserialize, Serialize};
+use std::io::{Result as IoResult, Write};
+
+/// How a sequence ended.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum SequenceOutcome {
+    Completed,
+    TimedOut,
+}
+
+/// A transition that matched a step of a sequence.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(bound(serialize = "N: Serialize", deserialize = "N: for<'a> Deserialize<'a>"))]
+pub struct SequenceStep<N: Network> {
+    // Block height the step was found at.
+    block_height: u32,
+    // Transaction the step was found in.
+    transaction: N::TransactionID,
+    // Transition matching the step.
+    transition: N::TransitionID,
+}
+
+impl<N: Network> SequenceStep<N> {
+    pub fn new(
+        block_height: u32,
+        transaction: N::TransactionID,
+        transition: N::TransitionID,
+    ) -> SequenceStep<N> {
+        SequenceStep {
+            block_height,
+            transaction,
+            transition,
+        }
+    }
+
+    /// Get the block height the step was found at.
+    pub fn block_height(&self) -> u32 {
+        self.block_height
+    }
+
+    /// Get the transaction the step was found in.
+    pub fn transaction(&self) -> &N::TransactionID {
+        &self.transaction
+    }
+}
+
+/// A sequence that completed or ran out of time.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(bound(serialize = "N: Serialize", deserialize = "N: for<'a> Deserialize<'a>"))]
+pub struct SequenceEvent<N: Network> {
+    // How the sequence ended.
+    outcome: SequenceOutcome,
+    // Steps matched, starting with the first.
+    steps: Vec<SequenceStep<N>>,
+}
+
+impl<N: Network> SequenceEvent<N> {
+    pub fn new(outcome: SequenceOutcome, steps: Vec<SequenceStep<N>>) -> SequenceEvent<N> {
+        SequenceEvent { outcome, steps }
+    }
+
+    /// Get the steps matched.
+    pub fn steps(&self) -> &[SequenceStep<N>] {
+        &self.steps
+    }
+}
+
+impl<N: Network> ToBytes for SequenceStep<N> {
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.block_height.write_le(&mut writer)?;
+        self.transaction.write_le(&mut writer)?;
+        self.transition.write_le(writer)
+    }
+}
+
+impl<N: Network> ToBytes for SequenceEvent<N> {
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        (self.outcome as u8).write_le(&mut writer)?;
+        write_list(&self.steps, writer)
+    }
+}
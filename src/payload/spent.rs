@@ -0,0 +1,64 @@
+use crate::ConfirmationStatus;
+use snarkvm::prelude::{Field, Identifier, Network, ProgramID, ToBytes};
+
+use serde::{Deserialize, Serialize};
+use std::io::{Result as IoResult, Write};
+
+/// A watched record consumed by a transition.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(bound(serialize = "N: Serialize", deserialize = "N: for<'a> Deserialize<'a>"))]
+pub struct RecordSpentEvent<N: Network> {
+    // Program that consumed the record.
+    program: ProgramID<N>,
+    // Function that consumed the record.
+    function_id: Identifier<N>,
+    // Transaction the record was spent in.
+    transaction: N::TransactionID,
+    // Transition the record was spent in.
+    transition: N::TransitionID,
+    // Whether the transaction was accepted or rejected.
+    status: ConfirmationStatus,
+    // Commitment of the spent record.
+    commitment: Field<N>,
+    // Serial number revealed by the spend.
+    serial_number: Field<N>,
+}
+
+impl<N: Network> RecordSpentEvent<N> {
+    pub fn new(
+        program: ProgramID<N>,
+        function_id: Identifier<N>,
+        transaction: N::TransactionID,
+        transition: N::TransitionID,
+        status: ConfirmationStatus,
+        commitment: Field<N>,
+        serial_number: Field<N>,
+    ) -> RecordSpentEvent<N> {
+        RecordSpentEvent {
+            program,
+            function_id,
+            transaction,
+            transition,
+            status,
+            commitment,
+            serial_number,
+        }
+    }
+
+    /// Get the transaction the event was found in.
+    pub fn transaction(&self) -> &N::TransactionID {
+        &self.transaction
+    }
+}
+
+impl<N: Network> ToBytes for RecordSpentEvent<N> {
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.program.write_le(&mut writer)?;
+        self.function_id.write_le(&mut writer)?;
+        self.transaction.write_le(&mut writer)?;
+        self.transition.write_le(&mut writer)?;
+        (self.status as u8).write_le(&mut writer)?;
+        self.commitment.write_le(&mut writer)?;
+        self.serial_number.write_le(writer)
+    }
+}
@@ -0,0 +1,352 @@
+use super::bytes::{write_length, write_list};
+use crate::ConfirmationStatus;
+use snarkvm::ledger::block::{Input, Output, Transition};
+use snarkvm::prelude::{Argument, Future, Identifier, Network, Plaintext, ProgramID, ToBytes};
+
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::io::{Result as IoResult, Write};
+
+/// A function call found in a block.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(bound(serialize = "N: Serialize", deserialize = "N: for<'a> Deserialize<'a>"))]
+pub struct TransitionEvent<N: Network> {
+    // Program executed.
+    program: ProgramID<N>,
+    // Function executed.
+    function_id: Identifier<N>,
+    // Transaction ID execution was found at.
+    transaction: N::TransactionID,
+    // Transition ID execution was found at.
+    transition: N::TransitionID,
+    // Whether the transaction was accepted or rejected.
+    status: ConfirmationStatus,
+    // Inputs triggered.
+    inputs: Option<IndexMap<u32, TransitionValue<N>>>,
+    // Outputs triggered.
+    outputs: Option<IndexMap<u32, TransitionValue<N>>>,
+}
+
+impl<N: Network> TransitionEvent<N> {
+    pub fn new(
+        program: ProgramID<N>,
+        function_id: Identifier<N>,
+        transaction: N::TransactionID,
+        transition: N::TransitionID,
+        status: ConfirmationStatus,
+        inputs: Option<IndexMap<u32, TransitionValue<N>>>,
+        outputs: Option<IndexMap<u32, TransitionValue<N>>>,
+    ) -> TransitionEvent<N> {
+        TransitionEvent {
+            program,
+            function_id,
+            transaction,
+            transition,
+            status,
+            inputs,
+            outputs,
+        }
+    }
+
+    /// Get the transaction the event was found in.
+    pub fn transaction(&self) -> &N::TransactionID {
+        &self.transaction
+    }
+}
+
+/// A transition input or output as reported in an event payload. Values the monitor cannot
+/// read are replaced by a placeholder naming the kind of value that was hidden.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(bound(serialize = "N: Serialize", deserialize = "N: for<'a> Deserialize<'a>"))]
+pub enum TransitionValue<N: Network> {
+    // A constant plaintext value.
+    Constant(Plaintext<N>),
+    // A public plaintext value.
+    Public(Plaintext<N>),
+    // A private value encrypted to its owner.
+    Private,
+    // A record owned by the caller or recipient.
+    Record,
+    // A record belonging to another program.
+    ExternalRecord,
+    // A future to be executed in finalize.
+    Future(FutureValue<N>),
+}
+
+impl<N: Network> TransitionValue<N> {
+    /// Collect the inputs of a transition keyed by position.
+    pub fn inputs(transition: &Transition<N>) -> IndexMap<u32, TransitionValue<N>> {
+        Self::collect(transition.inputs().iter().map(Self::from_input))
+    }
+
+    /// Collect the outputs of a transition keyed by position.
+    pub fn outputs(transition: &Transition<N>) -> IndexMap<u32, TransitionValue<N>> {
+        Self::collect(transition.outputs().iter().map(Self::from_output))
+    }
+
+    /// Returns the plaintext if the value is constant or public.
+    pub fn plaintext(&self) -> Option<&Plaintext<N>> {
+        match self {
+            Self::Constant(plaintext) | Self::Public(plaintext) => Some(plaintext),
+            _ => None,
+        }
+    }
+
+    /// Convert a transition input, skipping public values that were not included in the transition.
+    fn from_input(input: &Input<N>) -> Option<Self> {
+        match input {
+            Input::Constant(_, plaintext) => plaintext.clone().map(Self::Constant),
+            Input::Public(_, plaintext) => plaintext.clone().map(Self::Public),
+            Input::Private(..) => Some(Self::Private),
+            Input::Record(..) => Some(Self::Record),
+            Input::ExternalRecord(..) => Some(Self::ExternalRecord),
+        }
+    }
+
+    /// Convert a transition output, skipping public values that were not included in the transition.
+    fn from_output(output: &Output<N>) -> Option<Self> {
+        match output {
+            Output::Constant(_, plaintext) => plaintext.clone().map(Self::Constant),
+            Output::Public(_, plaintext) => plaintext.clone().map(Self::Public),
+            Output::Private(..) => Some(Self::Private),
+            Output::Record(..) => Some(Self::Record),
+            Output::ExternalRecord(..) => Some(Self::ExternalRecord),
+            Output::Future(_, future) => {
+                future.as_ref().map(|f| Self::Future(FutureValue::from(f)))
+            }
+        }
+    }
+
+    fn collect(
+        values: impl Iterator<Item = Option<TransitionValue<N>>>,
+    ) -> IndexMap<u32, TransitionValue<N>> {
+        values
+            .enumerate()
+            .filter_map(|(index, value)| Some((index as u32, value?)))
+            .collect()
+    }
+}
+
+/// A decoded future: the finalize call it schedules and its arguments.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(bound(serialize = "N: Serialize", deserialize = "N: for<'a> Deserialize<'a>"))]
+pub struct FutureValue<N: Network> {
+    // Program whose finalize block runs.
+    program: ProgramID<N>,
+    // Function whose finalize block runs.
+    function_id: Identifier<N>,
+    // Arguments passed to finalize.
+    arguments: Vec<FutureArgument<N>>,
+}
+
+impl<N: Network> FutureValue<N> {
+    /// Get the program whose finalize block runs.
+    pub fn program(&self) -> &ProgramID<N> {
+        &self.program
+    }
+
+    /// Get the function whose finalize block runs.
+    pub fn function_id(&self) -> &Identifier<N> {
+        &self.function_id
+    }
+
+    /// Get the arguments passed to finalize.
+    pub fn arguments(&self) -> &[FutureArgument<N>] {
+        &self.arguments
+    }
+}
+
+impl<N: Network> From<&Future<N>> for FutureValue<N> {
+    fn from(future: &Future<N>) -> Self {
+        FutureValue {
+            program: *future.program_id(),
+            function_id: *future.function_name(),
+            arguments: future
+                .arguments()
+                .iter()
+                .map(|argument| match argument {
+                    Argument::Plaintext(plaintext) => FutureArgument::Plaintext(plaintext.clone()),
+                    Argument::Future(future) => FutureArgument::Future(FutureValue::from(future)),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// An argument of a future: a plaintext value, or a future from a call to another program.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(bound(serialize = "N: Serialize", deserialize = "N: for<'a> Deserialize<'a>"))]
+pub enum FutureArgument<N: Network> {
+    Plaintext(Plaintext<N>),
+    Future(FutureValue<N>),
+}
+
+impl<N: Network> ToBytes for TransitionEvent<N> {
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.program.write_le(&mut writer)?;
+        self.function_id.write_le(&mut writer)?;
+        self.transaction.write_le(&mut writer)?;
+        self.transition.write_le(&mut writer)?;
+        (self.status as u8).write_le(&mut writer)?;
+        write_values(self.inputs.as_ref(), &mut writer)?;
+        write_values(self.outputs.as_ref(), writer)
+    }
+}
+
+/// Write optional indexed values as a list of entries sorted by index, so the encoding does not
+/// depend on the order a relay kept them in.
+fn write_values<N: Network, W: Write>(
+    values: Option<&IndexMap<u32, TransitionValue<N>>>,
+    mut writer: W,
+) -> IoResult<()> {
+    let Some(values) = values else {
+        return 0u8.write_le(writer);
+    };
+    1u8.write_le(&mut writer)?;
+    write_length(values.len(), &mut writer)?;
+    let mut entries = values.iter().collect::<Vec<_>>();
+    entries.sort_by_key(|(index, _)| **index);
+    entries.into_iter().try_for_each(|(index, value)| {
+        index.write_le(&mut writer)?;
+        value.write_le(&mut writer)
+    })
+}
+
+impl<N: Network> ToBytes for TransitionValue<N> {
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        match self {
+            Self::Constant(plaintext) => {
+                0u8.write_le(&mut writer)?;
+                plaintext.write_le(writer)
+            }
+            Self::Public(plaintext) => {
+                1u8.write_le(&mut writer)?;
+                plaintext.write_le(writer)
+            }
+            Self::Private => 2u8.write_le(writer),
+            Self::Record => 3u8.write_le(writer),
+            Self::ExternalRecord => 4u8.write_le(writer),
+            Self::Future(future) => {
+                5u8.write_le(&mut writer)?;
+                future.write_le(writer)
+            }
+        }
+    }
+}
+
+impl<N: Network> ToBytes for FutureValue<N> {
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.program.write_le(&mut writer)?;
+        self.function_id.write_le(&mut writer)?;
+        write_list(&self.arguments, writer)
+    }
+}
+
+impl<N: Network> ToBytes for FutureArgument<N> {
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        match self {
+            Self::Plaintext(plaintext) => {
+                0u8.write_le(&mut writer)?;
+                plaintext.write_le(writer)
+            }
+            Self::Future(future) => {
+                1u8.write_le(&mut writer)?;
+                future.write_le(writer)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monitor::test_helpers::{sample_ledger, CurrentNetwork};
+    use serde_json::json;
+    use snarkvm::prelude::{Address, Field, PrivateKey, TestRng};
+    use std::str::FromStr;
+
+    #[test]
+    fn test_transition_values() {
+        let rng = &mut TestRng::default();
+        let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let address = Address::try_from(&private_key).unwrap();
+        let ledger = sample_ledger(&private_key, rng);
+        let transactions = ledger.get_transactions(0).unwrap();
+        let transition = transactions
+            .iter()
+            .next()
+            .unwrap()
+            .transitions()
+            .next()
+            .unwrap()
+            .clone();
+        let amount = match &transition.inputs()[1] {
+            Input::Public(_, Some(amount)) => amount.to_string(),
+            input => panic!("expected a public amount, found {input:?}"),
+        };
+
+        // The receiver is private, the amount public, the new record and the future are
+        // replaced by placeholders or decoded.
+        let inputs = serde_json::to_value(TransitionValue::inputs(&transition)).unwrap();
+        assert_eq!(inputs, json!({"0": "Private", "1": {"Public": amount}}));
+        let outputs = serde_json::to_value(TransitionValue::outputs(&transition)).unwrap();
+        assert_eq!(
+            outputs,
+            json!({
+                "0": "Record",
+                "1": {"Future": {
+                    "program": "credits.aleo",
+                    "function_id": "transfer_public_to_private",
+                    "arguments": [
+                        {"Plaintext": address.to_string()},
+                        {"Plaintext": amount},
+                    ],
+                }},
+            })
+        );
+
+        // Public values left out of a transition are skipped, keeping the other indices.
+        let left_out = [
+            Input::<CurrentNetwork>::Public(Field::from_u64(1), None),
+            Input::Private(Field::from_u64(2), None),
+            Input::Constant(
+                Field::from_u64(3),
+                Some(Plaintext::from_str("3u8").unwrap()),
+            ),
+        ];
+        let values = TransitionValue::collect(left_out.iter().map(TransitionValue::from_input));
+        assert_eq!(
+            serde_json::to_value(values).unwrap(),
+            json!({"1": "Private", "2": {"Constant": "3u8"}})
+        );
+
+        // Futures awaited by a future are decoded as nested arguments.
+        let future = Future::<CurrentNetwork>::from_str(
+            "{ program_id: token.aleo, function_name: transfer_public, arguments: [ { program_id: credits.aleo, function_name: transfer_public, arguments: [ 5u64 ] }, 7field ] }",
+        )
+        .unwrap();
+        let output = Output::Future(Field::from_u64(4), Some(future));
+        assert_eq!(
+            serde_json::to_value(TransitionValue::from_output(&output)).unwrap(),
+            json!({"Future": {
+                "program": "token.aleo",
+                "function_id": "transfer_public",
+                "arguments": [
+                    {"Future": {
+                        "program": "credits.aleo",
+                        "function_id": "transfer_public",
+                        "arguments": [{"Plaintext": "5u64"}],
+                    }},
+                    {"Plaintext": "7field"},
+                ],
+            }})
+        );
+        assert!(
+            TransitionValue::from_output(&Output::<CurrentNetwork>::Future(
+                Field::from_u64(5),
+                None
+            ))
+            .is_none()
+        );
+    }
+}
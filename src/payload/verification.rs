@@ -0,0 +1,40 @@
+use super::bytes::write_string;
+use super::EventDetails;
+use snarkvm::prelude::{Network, ToBytes};
+
+use serde::{Deserialize, Serialize};
+use std::io::{Result as IoResult, Write};
+
+/// An event withheld because its transaction failed verification.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(bound(serialize = "N: Serialize", deserialize = "N: for<'a> Deserialize<'a>"))]
+pub struct VerificationFailedEvent<N: Network> {
+    // Transaction that failed verification.
+    transaction: N::TransactionID,
+    // Why verification failed.
+    error: String,
+    // The event that was withheld.
+    withheld: Box<EventDetails<N>>,
+}
+
+impl<N: Network> VerificationFailedEvent<N> {
+    pub fn new(
+        transaction: N::TransactionID,
+        error: String,
+        withheld: EventDetails<N>,
+    ) -> VerificationFailedEvent<N> {
+        VerificationFailedEvent {
+            transaction,
+            error,
+            withheld: Box::new(withheld),
+        }
+    }
+}
+
+impl<N: Network> ToBytes for VerificationFailedEvent<N> {
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.transaction.write_le(&mut writer)?;
+        write_string(&self.error, &mut writer)?;
+        self.withheld.write_le(writer)
+    }
+}
@@ -16,23 +16,37 @@
 use axum::{
     http::StatusCode,
     response::{IntoResponse, Response},
+    Json,
 };
+use serde_json::json;
 
 /// An enum of error handlers for the REST API server.
-pub struct RestError(pub String);
+pub enum RestError {
+    /// The request was invalid, with every problem found in it.
+    BadRequest(Vec<String>),
+    /// The request could not be handled.
+    Internal(String),
+}
 
 impl IntoResponse for RestError {
     fn into_response(self) -> Response {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            format!("Something went wrong: {}", self.0),
-        )
-            .into_response()
+        match self {
+            Self::BadRequest(problems) => (
+                StatusCode::BAD_REQUEST,
+                Json(json!({"error": "invalid request", "problems": problems})),
+            )
+                .into_response(),
+            Self::Internal(error) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Something went wrong: {error}"),
+            )
+                .into_response(),
+        }
     }
 }
 
 impl From<anyhow::Error> for RestError {
     fn from(err: anyhow::Error) -> Self {
-        Self(err.to_string())
+        Self::Internal(err.to_string())
     }
 }
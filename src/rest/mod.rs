@@ -13,7 +13,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use snarkvm::prelude::Network;
+use snarkvm::prelude::{Network, PrivateKey};
 
 mod helpers;
 pub use helpers::*;
@@ -29,7 +29,7 @@ use axum::{
     middleware,
     middleware::Next,
     response::Response,
-    routing::post,
+    routing::{get, post},
     Json,
 };
 use axum_extra::response::ErasedJson;
@@ -54,8 +54,18 @@ pub struct MonitorRestService<N: Network, C: ConsensusStorage<N>> {
 }
 
 impl<N: Network, C: ConsensusStorage<N>> MonitorRestService<N, C> {
-    /// Initializes a new instance of the server.
-    pub async fn start(monitor: Monitor<N, C>, rest_ip: SocketAddr, rest_rps: u32) -> Result<Self> {
+    /// Initializes a new instance of the server. Event payloads are signed with the private
+    /// key, if one is given.
+    pub async fn start(
+        monitor: Monitor<N, C>,
+        private_key: Option<PrivateKey<N>>,
+        rest_ip: SocketAddr,
+        rest_rps: u32,
+    ) -> Result<Self> {
+        let monitor = match private_key {
+            Some(private_key) => monitor.with_private_key(private_key)?,
+            None => monitor,
+        };
         // Initialize the server.
         let mut server = Self {
             monitor: Arc::new(Mutex::new(monitor)),
@@ -118,7 +128,9 @@ impl<N: Network, C: ConsensusStorage<N>> MonitorRestService<N, C> {
                     &format!("/{network}/subscribe"),
                     post(Self::start_subscription),
                 )
-                .route(&format!("/{network}/events"), post(Self::get_events));
+                .route(&format!("/{network}/events"), post(Self::get_events))
+                // GET - the address event payloads are signed by.
+                .route(&format!("/{network}/address"), get(Self::get_address));
 
             routes
                 // Pass in `Rest` to make things convenient.
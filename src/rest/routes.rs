@@ -26,22 +26,40 @@ impl<N: Network, C: ConsensusStorage<N>> MonitorRestService<N, C> {
     ) -> Result<ErasedJson, RestError> {
         // Start a new job.
         debug!("POST /subscribe");
+        // Reject manifests that could never match, listing every problem.
+        let problems = rest.monitor.lock().check_manifests(&manifest);
+        if !problems.is_empty() {
+            return Err(RestError::BadRequest(problems));
+        }
         let subscription = Subscription::new(manifest)?;
         let subscription_id = *subscription.id();
-        rest.monitor.lock().add(subscription);
+        rest.monitor.lock().add(subscription)?;
         Ok(ErasedJson::pretty(
             json!({"status": "event monitor started", "subscription_id": subscription_id}),
         ))
     }
 
+    /// GET /<network>/address
+    pub(crate) async fn get_address(State(rest): State<Self>) -> Result<ErasedJson, RestError> {
+        // Null if the monitor does not sign payloads.
+        let address = rest.monitor.lock().address();
+        Ok(ErasedJson::pretty(json!({"address": address})))
+    }
+
     /// POST /<network>/events
+    /// Drain the events of a subscription, along with the state of their webhook deliveries
+    /// keyed by event `id`. Deliveries still pending for events drained earlier are included.
     pub(crate) async fn get_events(
         State(rest): State<Self>,
         Json(id): Json<SubscriptionID<N>>,
     ) -> Result<ErasedJson, RestError> {
-        let (id, events) = rest.monitor.lock().drain(id);
+        let (id, events, deliveries) = {
+            let mut monitor = rest.monitor.lock();
+            let (id, events) = monitor.drain(id);
+            (id, events, monitor.deliveries(&id))
+        };
         Ok(ErasedJson::pretty(
-            json!({"subscription": id, "events": events}),
+            json!({"subscription": id, "events": events, "deliveries": deliveries}),
         ))
     }
 }
@@ -1,4 +1,4 @@
-use snarkvm::prelude::{Field, Network, Uniform};
+use snarkvm::prelude::{Field, GraphKey, Network, Uniform, ViewKey};
 
 use anyhow::Result;
 use rand::thread_rng;
@@ -21,6 +21,9 @@ pub struct Subscription<N: Network> {
 impl<N: Network> Subscription<N> {
     /// Create a new ETL job.
     pub fn new(events: EventManifests<N>) -> Result<Self> {
+        // Reject manifests with ill-typed predicates.
+        events.check()?;
+
         // Create a nonce and domains separator for the etl job.
         let nonce = N::hash_psd4(&[Field::<N>::rand(&mut thread_rng())])?;
         let domain_separator = Field::new_domain_separator(DOMAIN_SEPARATOR);
@@ -42,6 +45,21 @@ impl<N: Network> Subscription<N> {
     pub fn events(&self) -> &Vec<EventManifest<N>> {
         self.events.manifests()
     }
+
+    /// Get the view key used to decrypt records.
+    pub fn view_key(&self) -> Option<&ViewKey<N>> {
+        self.events.view_key()
+    }
+
+    /// Get the graph key used to detect spent records.
+    pub fn graph_key(&self) -> Option<GraphKey<N>> {
+        self.events.graph_key()
+    }
+
+    /// Returns `true` if matched transactions are re-verified before events are reported.
+    pub fn is_paranoid(&self) -> bool {
+        self.events.is_paranoid()
+    }
 }
 
 impl<N: Network> Display for Subscription<N> {